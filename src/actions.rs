@@ -0,0 +1,140 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+
+use miette::{IntoDiagnostic, Report, Result};
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::MuError;
+use crate::publish::{Platform, Publication};
+
+const TAG_REF_PREFIX: &str = "refs/tags/";
+
+#[derive(Deserialize)]
+struct Event {
+    release: Option<EventRelease>,
+}
+
+#[derive(Deserialize)]
+struct EventRelease {
+    tag_name: String,
+}
+
+/// Checks whether the tool is running in GitHub Actions.
+pub fn is_github_actions() -> bool {
+    std::env::var("GITHUB_ACTIONS").as_deref() == Ok("true")
+}
+
+/// Infers the release tag from the event payload or the ref
+/// that triggered the workflow.
+pub async fn infer_tag() -> Result<Option<String>> {
+    if let Ok(path) = std::env::var("GITHUB_EVENT_PATH") {
+        let text = tokio::fs::read_to_string(&path).await.map_err(|err| {
+            MuError::new(format!("Could not read event payload {}", path))
+                .cause(err)
+                .to_report()
+        })?;
+        let event: Event = serde_json::from_str(&text).into_diagnostic()?;
+
+        if let Some(release) = event.release {
+            return Ok(Some(release.tag_name));
+        }
+    }
+
+    Ok(std::env::var("GITHUB_REF")
+        .ok()
+        .and_then(|git_ref| git_ref.strip_prefix(TAG_REF_PREFIX).map(String::from)))
+}
+
+/// Formats an error as an `::error` workflow command.
+pub fn error_annotation(report: &Report) -> String {
+    let message = report
+        .chain()
+        .map(|err| err.to_string())
+        .collect::<Vec<_>>()
+        .join(": ");
+    format!("::error::{}", escape_data(&message))
+}
+
+// See https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts
+fn escape_data(str: &str) -> String {
+    str.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Writes a Markdown summary of the publications to `GITHUB_STEP_SUMMARY`
+/// and their IDs to `GITHUB_OUTPUT`.
+pub async fn write_results(tag: &str, publications: &[Publication]) -> Result<()> {
+    if let Ok(path) = std::env::var("GITHUB_STEP_SUMMARY") {
+        append(path, &summary(tag, publications)).await?;
+    }
+
+    if let Ok(path) = std::env::var("GITHUB_OUTPUT") {
+        let ids = |platform: Platform| {
+            publications
+                .iter()
+                .filter(|publication| publication.platform == platform)
+                .map(|publication| publication.id.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let outputs = format!(
+            "modrinth_version_ids={}\ncurseforge_file_ids={}\n",
+            ids(Platform::Modrinth),
+            ids(Platform::CurseForge)
+        );
+        append(path, &outputs).await?;
+    }
+
+    Ok(())
+}
+
+fn summary(tag: &str, publications: &[Publication]) -> String {
+    let mut result = format!("### Published {}\n\n", tag);
+
+    if publications.is_empty() {
+        result += "Nothing was published.\n";
+        return result;
+    }
+
+    result += "| Platform | Project | Version |\n";
+    result += "| --- | --- | --- |\n";
+
+    for publication in publications {
+        let version = if let Some(url) = &publication.url {
+            format!("[{}]({})", publication.id, url)
+        } else {
+            publication.id.clone()
+        };
+        result += format!(
+            "| {} | {} | {} |\n",
+            publication.platform.name(),
+            publication.project,
+            version
+        )
+        .as_str();
+    }
+
+    result
+}
+
+async fn append<P: AsRef<Path>>(path: P, text: &str) -> Result<()> {
+    let path = path.as_ref();
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|err| {
+            MuError::new(format!("Could not open {}", path.display()))
+                .cause(err)
+                .to_report()
+        })?;
+    file.write_all(text.as_bytes()).await.into_diagnostic()
+}
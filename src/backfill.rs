@@ -0,0 +1,364 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use miette::{miette, IntoDiagnostic, Result};
+use sha1::{Digest, Sha1};
+
+use crate::config::{Config, ModrinthSettings, Project};
+use crate::github::write_release_links;
+use crate::modrinth::{
+    find_existing_version, get_publication, get_version_number, ModrinthCache, Version,
+};
+use crate::publish::{check_assets, publish_project, validate_projects, Platform};
+use crate::requests::Context;
+use crate::source::{AssetStore, Release, ReleaseSource};
+
+/// Selects which historical releases are mirrored by [`backfill`].
+#[derive(Default)]
+pub struct BackfillOptions {
+    /// The oldest tag to include.
+    pub from_tag: Option<String>,
+    /// The newest tag to include.
+    pub to_tag: Option<String>,
+    /// The earliest publication time to include.
+    pub since: Option<DateTime<Utc>>,
+    /// The latest publication time to include.
+    pub until: Option<DateTime<Utc>>,
+    /// The maximum number of releases to mirror, counting from the newest one.
+    pub limit: Option<usize>,
+    /// The platforms to mirror to.
+    pub platforms: Vec<Platform>,
+    /// Upload to CurseForge even though existing files can't be detected.
+    pub force: bool,
+}
+
+/// Mirrors historical releases oldest first, skipping the ones
+/// that already exist on the target platforms.
+pub async fn backfill(
+    context: &Context,
+    source: &AssetStore<'_>,
+    config: &Config,
+    options: &BackfillOptions,
+) -> Result<()> {
+    config.reject_publish_at("backfill")?;
+    let releases = select_releases(source.list_releases(context).await?, options)?;
+
+    if releases.is_empty() {
+        return Err(miette!("No releases matched the backfill range"));
+    }
+
+    for release in &releases {
+        check_assets(release)?;
+    }
+
+    let mut cache = ModrinthCache::default();
+    validate_projects(context, config, &options.platforms, &mut cache).await?;
+
+    let uses_curseforge = options.platforms.contains(&Platform::CurseForge)
+        && config
+            .get_projects()
+            .iter()
+            .any(|project| project.get_curseforge(config).is_some());
+
+    if uses_curseforge && !options.force {
+        return Err(miette!(
+            "Existing CurseForge files cannot be detected, pass --force to upload every selected release \
+             or --platform modrinth to skip CurseForge"
+        ));
+    }
+
+    if uses_curseforge {
+        context
+            .progress
+            .println(format!(
+                "{} existing CurseForge files cannot be detected, all selected releases will be uploaded",
+                console::style("Warning:").bold().yellow()
+            ))
+            .into_diagnostic()?;
+    }
+
+    let links_repo = config.get_release_links_repo()?;
+
+    for release in &releases {
+        let mut publications = Vec::new();
+
+        for project in config.get_projects() {
+            let mut platforms = options.platforms.clone();
+
+            if let Some(settings) = project.get_modrinth(config) {
+                if platforms.contains(&Platform::Modrinth) {
+                    let modrinth_project = cache.project(context, settings).await?;
+                    let versions = cache.versions(context, settings).await?;
+                    let existing = find_on_modrinth(
+                        context, source, config, &project, release, settings, &versions,
+                    )
+                    .await?;
+
+                    if let Some(existing) = existing {
+                        context
+                            .progress
+                            .println(format!(
+                                "Skipping {} for Modrinth project {}: already exists",
+                                release.tag_name, settings.project_id
+                            ))
+                            .into_diagnostic()?;
+                        platforms.retain(|platform| *platform != Platform::Modrinth);
+                        publications.push(get_publication(settings, &modrinth_project, existing));
+                    }
+                }
+            }
+
+            publications.extend(
+                publish_project(
+                    context, source, config, &project, release, &platforms, &mut cache,
+                )
+                .await?,
+            );
+        }
+
+        if let Some(repo) = &links_repo {
+            write_release_links(context, repo, release, &publications).await?;
+        }
+
+        source.evict(release);
+    }
+
+    Ok(())
+}
+
+/// Filters and orders the releases (listed newest first by the source)
+/// into the oldest-first list of releases to mirror.
+fn select_releases(releases: Vec<Release>, options: &BackfillOptions) -> Result<Vec<Release>> {
+    let mut releases: Vec<Release> = releases
+        .into_iter()
+        .rev()
+        .filter(|release| !release.draft)
+        .collect();
+
+    if let Some(tag) = &options.from_tag {
+        let start = find_tag(&releases, tag)?;
+        releases.drain(..start);
+    }
+
+    if let Some(tag) = &options.to_tag {
+        let end = find_tag(&releases, tag)?;
+        releases.truncate(end + 1);
+    }
+
+    if options.since.is_some() || options.until.is_some() {
+        releases.retain(|release| {
+            // Releases without a publication time can't be in a date range.
+            let Some(published_at) = release
+                .published_at
+                .as_deref()
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            else {
+                return false;
+            };
+
+            options.since.is_none_or(|since| published_at >= since)
+                && options.until.is_none_or(|until| published_at <= until)
+        });
+    }
+
+    if let Some(limit) = options.limit {
+        let skipped = releases.len().saturating_sub(limit);
+        releases.drain(..skipped);
+    }
+
+    Ok(releases)
+}
+
+/// Parses the start of a date range, either a `YYYY-MM-DD` date
+/// that starts at midnight UTC or an RFC 3339 timestamp.
+pub fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_bound(value, NaiveTime::MIN)
+}
+
+/// Parses the end of a date range, either a `YYYY-MM-DD` date
+/// that includes the whole day in UTC or an RFC 3339 timestamp.
+pub fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    let end_of_day = NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap();
+    parse_bound(value, end_of_day)
+}
+
+fn parse_bound(value: &str, time: NaiveTime) -> Result<DateTime<Utc>, String> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .filter(|_| value.len() == 10)
+        .map(|date| date.and_time(time).and_utc())
+        .ok_or_else(|| {
+            format!(
+                "expected YYYY-MM-DD or an RFC 3339 timestamp, found {}",
+                value
+            )
+        })
+}
+
+fn find_tag(releases: &[Release], tag: &str) -> Result<usize> {
+    releases
+        .iter()
+        .position(|release| release.tag_name == tag)
+        .ok_or_else(|| miette!("Tag {} not found among the releases", tag))
+}
+
+/// Finds an existing version of a release in a list of Modrinth versions,
+/// first by version number and loaders and then by the SHA-1 hashes of the assets.
+async fn find_on_modrinth<'a>(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    project: &Project,
+    release: &Release,
+    settings: &ModrinthSettings,
+    versions: &'a [Version],
+) -> Result<Option<&'a Version>> {
+    let version_number = get_version_number(settings, release)?;
+    let loaders: Vec<String> = project
+        .get_loaders(config)?
+        .iter()
+        .map(|loader| loader.modrinth_id().to_string())
+        .collect();
+
+    if let Some(version) = find_existing_version(versions, &version_number, &loaders)? {
+        return Ok(Some(version));
+    }
+
+    let hashes: HashMap<&str, &Version> = versions
+        .iter()
+        .flat_map(|version| {
+            version
+                .files
+                .iter()
+                .map(move |file| (file.hashes.sha1.as_str(), version))
+        })
+        .collect();
+
+    if hashes.is_empty() {
+        return Ok(None);
+    }
+
+    let file_regex = project.get_regex(config)?;
+    for asset in release.get_assets(&file_regex) {
+        let bytes = source.download_asset(context, asset).await?;
+        if let Some(version) = hashes.get(hex::encode(Sha1::digest(&bytes)).as_str()) {
+            return Ok(Some(version));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag_name: &str, published_at: Option<&str>) -> Release {
+        Release {
+            id: None,
+            tag_name: tag_name.to_string(),
+            name: None,
+            body: None,
+            prerelease: false,
+            draft: published_at.is_none(),
+            published_at: published_at.map(str::to_string),
+            assets: Vec::new(),
+        }
+    }
+
+    /// Releases newest first, as listed by the sources.
+    fn releases() -> Vec<Release> {
+        vec![
+            release("1.3.0", Some("2024-03-01T12:00:00Z")),
+            release("1.2.0", Some("2024-02-01T12:00:00Z")),
+            release("1.1.0", Some("2024-01-31T23:30:00Z")),
+            release("1.0.0", Some("2024-01-01T00:00:00Z")),
+        ]
+    }
+
+    fn tags(releases: &[Release]) -> Vec<&str> {
+        releases
+            .iter()
+            .map(|release| release.tag_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn selects_all_releases_oldest_first() {
+        let mut releases = releases();
+        releases.insert(0, release("2.0.0", None));
+        let selected = select_releases(releases, &BackfillOptions::default()).unwrap();
+        assert_eq!(tags(&selected), ["1.0.0", "1.1.0", "1.2.0", "1.3.0"]);
+    }
+
+    #[test]
+    fn selects_tag_range() {
+        let options = BackfillOptions {
+            from_tag: Some("1.1.0".to_string()),
+            to_tag: Some("1.2.0".to_string()),
+            ..Default::default()
+        };
+        let selected = select_releases(releases(), &options).unwrap();
+        assert_eq!(tags(&selected), ["1.1.0", "1.2.0"]);
+    }
+
+    #[test]
+    fn rejects_unknown_tag() {
+        let options = BackfillOptions {
+            from_tag: Some("0.9.0".to_string()),
+            ..Default::default()
+        };
+        assert!(select_releases(releases(), &options).is_err());
+    }
+
+    #[test]
+    fn selects_date_range() {
+        let options = BackfillOptions {
+            since: Some(parse_since("2024-01-02").unwrap()),
+            until: Some(parse_until("2024-01-31").unwrap()),
+            ..Default::default()
+        };
+        let selected = select_releases(releases(), &options).unwrap();
+        assert_eq!(tags(&selected), ["1.1.0"]);
+    }
+
+    #[test]
+    fn limits_to_newest_releases() {
+        let options = BackfillOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let selected = select_releases(releases(), &options).unwrap();
+        assert_eq!(tags(&selected), ["1.2.0", "1.3.0"]);
+    }
+
+    #[test]
+    fn parses_bounds() {
+        assert_eq!(
+            parse_since("2024-01-31").unwrap().to_rfc3339(),
+            "2024-01-31T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_until("2024-01-31").unwrap().to_rfc3339(),
+            "2024-01-31T23:59:59.999999999+00:00"
+        );
+        assert_eq!(
+            parse_since("2024-01-31T12:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2024-01-31T10:00:00+00:00"
+        );
+        assert!(parse_since("2024-1-31").is_err());
+        assert!(parse_since("31.01.2024").is_err());
+    }
+}
@@ -4,7 +4,9 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::curseforge::ProjectRelation;
+use crate::config::Loader;
+use crate::curseforge::{ProjectClass, ProjectRelation};
+use miette::{miette, Result};
 use serde::Deserialize;
 
 #[derive(Deserialize, Clone)]
@@ -12,4 +14,40 @@ pub struct CurseForgeSettings {
     pub project_id: String,
     pub relations: Option<Vec<ProjectRelation>>,
     pub slug: Option<String>,
+    pub class: Option<ProjectClass>,
+}
+
+impl CurseForgeSettings {
+    pub fn get_class(&self) -> ProjectClass {
+        self.class.unwrap_or_default()
+    }
+
+    /// Checks that the loaders can be used with the project class.
+    pub fn validate_loaders(&self, loaders: &[Loader]) -> Result<()> {
+        let class = self.get_class();
+
+        for loader in loaders {
+            if !class.supports(*loader) {
+                return Err(miette!(
+                    "Loader {} cannot be used for CurseForge project {}, which is in class {:?}",
+                    loader.modrinth_id(),
+                    self.project_id,
+                    class
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the URL of the project's page if the slug is known.
+    pub fn get_url(&self) -> Option<String> {
+        self.slug.as_ref().map(|slug| {
+            format!(
+                "https://curseforge.com/minecraft/{}/{}",
+                self.get_class().url_segment(),
+                slug
+            )
+        })
+    }
 }
@@ -4,6 +4,8 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::path::Path;
+
 use miette::{miette, IntoDiagnostic, Result};
 use regex::Regex;
 use serde::Deserialize;
@@ -12,8 +14,10 @@ pub use curseforge::*;
 pub use modrinth::*;
 
 use crate::curseforge::ReleaseType;
-use crate::github::Release;
+use crate::error::MuError;
+use crate::github::Repo;
 use crate::modrinth::VersionType;
+use crate::source::{Asset, Release};
 
 mod curseforge;
 mod modrinth;
@@ -21,7 +25,15 @@ mod modrinth;
 #[derive(Deserialize, Clone)]
 pub struct Config {
     /// GitHub project (format: "owner/repo")
-    pub github: String,
+    pub github: Option<String>,
+    /// GitLab project (format: "group/project")
+    pub gitlab: Option<String>,
+    /// GitLab instance URL (default: https://gitlab.com)
+    pub gitlab_url: Option<String>,
+    /// Gitea or Forgejo repository (format: "owner/repo")
+    pub gitea: Option<String>,
+    /// Gitea or Forgejo instance URL (default: https://codeberg.org)
+    pub gitea_url: Option<String>,
     /// Target loaders
     pub loaders: Option<Vec<Loader>>,
     /// CurseForge project ID
@@ -34,10 +46,106 @@ pub struct Config {
     pub game_versions: Option<Vec<String>>,
     /// File regex
     pub file_regex: Option<String>,
+    /// Primary file regex
+    pub primary_file_regex: Option<String>,
     /// Release level
     pub release_level: Option<ReleaseLevel>,
+    /// Write links to the published versions into the GitHub release body
+    pub release_links: Option<bool>,
+}
+
+impl Config {
+    /// Reads a config from a TOML file.
+    pub async fn read(path: &Path) -> Result<Config> {
+        let text = tokio::fs::read_to_string(path).await.map_err(|err| {
+            MuError::new(format!("Could not read config file {}", path.display()))
+                .cause(err)
+                .to_report()
+        })?;
+        let config: Config = toml::from_str(&text).into_diagnostic()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the settings that can be checked without any requests.
+    fn validate(&self) -> Result<()> {
+        for project in self.get_projects() {
+            let Some(settings) = project.get_modrinth(self) else {
+                continue;
+            };
+
+            for dependency in settings.dependencies.iter().flatten() {
+                if let (Some(project_id), Some(slug)) = (&dependency.project_id, &dependency.slug) {
+                    return Err(miette!(
+                        "Dependency {} of Modrinth project {} has both a project_id and a slug ({})",
+                        project_id,
+                        settings.project_id,
+                        slug
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no Modrinth project sets `publish_at`, for commands that
+    /// publish several releases where a single publish time makes no sense.
+    pub fn reject_publish_at(&self, command: &str) -> Result<()> {
+        for project in self.get_projects() {
+            if let Some(settings) = project.get_modrinth(self) {
+                if settings.publish_at.is_some() {
+                    return Err(miette!(
+                        "publish_at of Modrinth project {} can't be used with {}",
+                        settings.project_id,
+                        command
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the configured GitHub repository.
+    pub fn get_github_repo(&self) -> Result<Repo> {
+        let repo = self
+            .github
+            .as_ref()
+            .ok_or_else(|| miette!("No GitHub repository defined!"))?;
+        Repo::parse(repo)
+    }
+
+    /// Gets the GitHub repository whose release bodies should get links
+    /// to the published versions, if enabled.
+    pub fn get_release_links_repo(&self) -> Result<Option<Repo>> {
+        if !self.release_links.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        if self.gitlab.is_some() || self.gitea.is_some() {
+            return Err(miette!(
+                "Release links can only be written to GitHub releases"
+            ));
+        }
+
+        self.get_github_repo().map(Some)
+    }
+
+    /// Gets the configured projects, or a single empty project
+    /// that inherits all top-level settings if there are none.
+    pub fn get_projects(&self) -> Vec<Project> {
+        if let Some(projects) = &self.projects {
+            projects.clone()
+        } else {
+            vec![Project::empty()]
+        }
+    }
 }
 
+/// Suffixes of files that aren't chosen as the primary file by default.
+const SECONDARY_FILE_SUFFIXES: [&str; 2] = ["-sources.jar", "-javadoc.jar"];
+
 #[derive(Deserialize, Clone)]
 pub struct Project {
     /// Target loaders
@@ -50,6 +158,8 @@ pub struct Project {
     pub game_versions: Option<Vec<String>>,
     /// File regex
     pub file_regex: Option<String>,
+    /// Primary file regex
+    pub primary_file_regex: Option<String>,
 }
 
 impl Project {
@@ -60,6 +170,7 @@ impl Project {
             modrinth: None,
             game_versions: None,
             file_regex: None,
+            primary_file_regex: None,
         }
     }
 
@@ -73,6 +184,44 @@ impl Project {
         Ok(regex)
     }
 
+    /// Gets the assets of a release that match the file regex,
+    /// with the primary file first.
+    ///
+    /// Without a primary file regex, the first asset that isn't
+    /// a sources or javadoc jar is the primary file.
+    pub fn get_assets<'a>(&self, config: &Config, release: &'a Release) -> Result<Vec<&'a Asset>> {
+        let mut assets = release.get_assets(&self.get_regex(config)?);
+
+        if assets.is_empty() {
+            return Ok(assets);
+        }
+
+        let primary = if let Some(regex) = self
+            .primary_file_regex
+            .as_ref()
+            .or(config.primary_file_regex.as_ref())
+        {
+            let regex = Regex::new(regex).into_diagnostic()?;
+            assets
+                .iter()
+                .position(|asset| regex.is_match(&asset.name))
+                .ok_or_else(|| miette!("No asset matches the primary file regex {}", regex))?
+        } else {
+            assets
+                .iter()
+                .position(|asset| {
+                    !SECONDARY_FILE_SUFFIXES
+                        .iter()
+                        .any(|suffix| asset.name.ends_with(suffix))
+                })
+                .unwrap_or(0)
+        };
+
+        let primary = assets.remove(primary);
+        assets.insert(0, primary);
+        Ok(assets)
+    }
+
     pub fn get_game_versions(&self, config: &Config) -> Result<Vec<String>> {
         self.game_versions
             .clone()
@@ -87,11 +236,11 @@ impl Project {
             .ok_or(miette!("No loaders defined!"))
     }
 
-    pub fn get_curseforge<'a>(&'a self, config: &'a Config) -> Option<&CurseForgeSettings> {
+    pub fn get_curseforge<'a>(&'a self, config: &'a Config) -> Option<&'a CurseForgeSettings> {
         self.curseforge.as_ref().or(config.curseforge.as_ref())
     }
 
-    pub fn get_modrinth<'a>(&'a self, config: &'a Config) -> Option<&ModrinthSettings> {
+    pub fn get_modrinth<'a>(&'a self, config: &'a Config) -> Option<&'a ModrinthSettings> {
         self.modrinth.as_ref().or(config.modrinth.as_ref())
     }
 }
@@ -100,6 +249,10 @@ impl Project {
 pub struct Secrets {
     pub github_token: String,
     pub curseforge_token: Option<String>,
+    pub gitlab_token: Option<String>,
+    pub gitea_token: Option<String>,
+    /// The secret used to verify GitHub webhook deliveries.
+    pub webhook_secret: Option<String>,
 }
 
 impl Secrets {
@@ -146,17 +299,36 @@ impl ReleaseLevel {
     }
 }
 
-#[derive(Deserialize, Clone, Copy)]
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum Loader {
     Fabric,
     Forge,
     Quilt,
+    /// Resource packs
+    Minecraft,
+    /// Data packs
+    Datapack,
+    /// Shader packs
+    Iris,
+    Optifine,
+    Canvas,
+    Vanilla,
 }
 
 impl Loader {
     pub fn values() -> Vec<Self> {
-        vec![Self::Fabric, Self::Forge, Self::Quilt]
+        vec![
+            Self::Fabric,
+            Self::Forge,
+            Self::Quilt,
+            Self::Minecraft,
+            Self::Datapack,
+            Self::Iris,
+            Self::Optifine,
+            Self::Canvas,
+            Self::Vanilla,
+        ]
     }
 
     pub fn modrinth_id(&self) -> &'static str {
@@ -164,14 +336,36 @@ impl Loader {
             Self::Fabric => "fabric",
             Self::Forge => "forge",
             Self::Quilt => "quilt",
+            Self::Minecraft => "minecraft",
+            Self::Datapack => "datapack",
+            Self::Iris => "iris",
+            Self::Optifine => "optifine",
+            Self::Canvas => "canvas",
+            Self::Vanilla => "vanilla",
         }
     }
 
-    pub fn curseforge_name(&self) -> &'static str {
+    /// The name of the CurseForge game version that marks this loader,
+    /// if CurseForge has one.
+    pub fn curseforge_name(&self) -> Option<&'static str> {
         match self {
-            Self::Fabric => "Fabric",
-            Self::Forge => "Forge",
-            Self::Quilt => "Quilt",
+            Self::Fabric => Some("Fabric"),
+            Self::Forge => Some("Forge"),
+            Self::Quilt => Some("Quilt"),
+            _ => None,
         }
     }
+
+    /// Checks whether this loader is for mods.
+    pub fn is_mod_loader(&self) -> bool {
+        matches!(self, Self::Fabric | Self::Forge | Self::Quilt)
+    }
+
+    /// Checks whether this loader is for shader packs.
+    pub fn is_shader_loader(&self) -> bool {
+        matches!(
+            self,
+            Self::Iris | Self::Optifine | Self::Canvas | Self::Vanilla
+        )
+    }
 }
@@ -4,13 +4,133 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use crate::modrinth::Dependency;
+use crate::modrinth::{DependencyType, FileType, VersionStatus, DEFAULT_API_URL};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
 use serde::Deserialize;
+use std::path::PathBuf;
 
 #[derive(Deserialize, Clone)]
 pub struct ModrinthSettings {
     pub project_id: String,
-    pub dependencies: Option<Vec<Dependency>>,
+    pub dependencies: Option<Vec<ModrinthDependency>>,
     pub version_number: Option<String>,
     pub slug: Option<String>,
+    pub on_existing: Option<OnExisting>,
+    pub featured: Option<bool>,
+    pub status: Option<VersionStatus>,
+    pub requested_status: Option<VersionStatus>,
+    /// The time at which the version is published. Until then, the version
+    /// has the configured status, or is a draft if there is none.
+    ///
+    /// Uploads are rejected once the time has passed, and the `backfill`, `watch`
+    /// and `serve` commands reject it, so this only makes sense for one-off publishes.
+    pub publish_at: Option<DateTime<Utc>>,
+    pub file_types: Option<Vec<FileTypeRule>>,
+    /// The base URL of the Modrinth API, for example `https://staging-api.modrinth.com/v2`.
+    pub api_url: Option<String>,
+    /// The project page content synced by the `sync-page` command.
+    pub page: Option<ProjectPageSettings>,
+    /// The images uploaded by the `gallery` command.
+    pub gallery: Option<Vec<GalleryImageSettings>>,
+}
+
+impl ModrinthSettings {
+    pub fn api_url(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .unwrap_or(DEFAULT_API_URL)
+            .trim_end_matches('/')
+    }
+
+    /// Gets the URL of the Modrinth website that matches the API URL.
+    ///
+    /// The `api.` or `-api` part of the API host is removed, so that
+    /// `staging-api.modrinth.com` becomes `staging.modrinth.com`.
+    /// Other hosts are used as is.
+    pub fn site_url(&self) -> String {
+        let Ok(url) = Url::parse(self.api_url()) else {
+            return self.api_url().to_string();
+        };
+        let host = url.host_str().unwrap_or_default();
+        let host = if let Some(host) = host.strip_prefix("api.") {
+            host.to_string()
+        } else {
+            host.replacen("-api.", ".", 1)
+        };
+        let port = url
+            .port()
+            .map_or(String::new(), |port| format!(":{}", port));
+        format!("{}://{}{}", url.scheme(), host, port)
+    }
+}
+
+/// What to do when a version with the same version number
+/// already exists on Modrinth.
+#[derive(Deserialize, Copy, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnExisting {
+    /// Upload another version with the same version number.
+    #[default]
+    Create,
+    /// Keep the existing version as is.
+    Skip,
+    /// Fail the upload.
+    Error,
+    /// Update the metadata of the existing version.
+    Update,
+}
+
+/// A dependency of a Modrinth version. The project and version can be given
+/// as IDs or resolved from a slug and a version number or requirement.
+#[derive(Deserialize, Clone)]
+pub struct ModrinthDependency {
+    #[serde(default)]
+    pub dependency_type: DependencyType,
+    pub file_name: Option<String>,
+    pub project_id: Option<String>,
+    pub version_id: Option<String>,
+    /// The slug of the project, used instead of `project_id`. Only one of them can be set.
+    pub slug: Option<String>,
+    /// A semver requirement that the newest compatible version must match.
+    pub version: Option<String>,
+    /// The exact version number of the dependency.
+    pub version_number: Option<String>,
+}
+
+/// Assigns a Modrinth file type to the files whose names match a regex.
+#[derive(Deserialize, Clone)]
+pub struct FileTypeRule {
+    pub regex: String,
+    pub file_type: FileType,
+}
+
+/// The content of a Modrinth project page. Only the fields
+/// that are set are synced.
+#[derive(Deserialize, Clone)]
+pub struct ProjectPageSettings {
+    /// A Markdown file used as the project body.
+    pub body_file: Option<PathBuf>,
+    /// The branch, tag or commit that relative links in the body point to.
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    pub summary: Option<String>,
+    pub categories: Option<Vec<String>>,
+    pub additional_categories: Option<Vec<String>>,
+    /// An SPDX license ID.
+    pub license: Option<String>,
+    pub source_url: Option<String>,
+    pub issues_url: Option<String>,
+    pub wiki_url: Option<String>,
+    pub discord_url: Option<String>,
+}
+
+/// An image in the gallery of a Modrinth project.
+#[derive(Deserialize, Clone)]
+pub struct GalleryImageSettings {
+    pub file: PathBuf,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub featured: bool,
 }
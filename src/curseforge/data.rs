@@ -6,6 +6,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::Loader;
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ReleaseType {
@@ -14,6 +16,39 @@ pub enum ReleaseType {
     Alpha,
 }
 
+/// The class of a CurseForge project.
+#[derive(Deserialize, Copy, Clone, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectClass {
+    #[default]
+    Mods,
+    ResourcePacks,
+    DataPacks,
+    Shaders,
+}
+
+impl ProjectClass {
+    /// The path segment of this class in CurseForge URLs.
+    pub fn url_segment(&self) -> &'static str {
+        match self {
+            Self::Mods => "mc-mods",
+            Self::ResourcePacks => "texture-packs",
+            Self::DataPacks => "data-packs",
+            Self::Shaders => "shaders",
+        }
+    }
+
+    /// Checks whether projects of this class can be published for a loader.
+    pub fn supports(&self, loader: Loader) -> bool {
+        match self {
+            Self::Mods => loader.is_mod_loader(),
+            Self::ResourcePacks => loader == Loader::Minecraft,
+            Self::DataPacks => loader == Loader::Datapack,
+            Self::Shaders => loader.is_shader_loader(),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct GameVersionType {
     pub id: u32,
@@ -12,10 +12,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::config::{Config, CurseForgeSettings, Project, ReleaseLevel};
 use crate::curseforge::{GameVersion, GameVersionType, Relations, ReleaseType};
-use crate::github::{Asset, GetAsset, Release};
+use crate::github::without_links_section;
 use crate::progress::simple_progress_bar_style;
+use crate::publish::{Platform, Publication};
 use crate::requests::multipart::Form;
-use crate::requests::{body_with_progress, ApiRequest, Context};
+use crate::requests::{
+    body_with_progress, send_rate_limited_with, ApiRequest, Context, RateLimitedSend,
+};
+use crate::source::{Asset, Release, ReleaseSource};
 
 const API_URL: &str = "https://minecraft.curseforge.com/api";
 const AUTH_KEY: &str = "X-Api-Token";
@@ -30,9 +34,8 @@ impl ApiRequest<Vec<GameVersionType>> for GameVersionTypes {
             .client
             .get(url)
             .header(AUTH_KEY, context.secrets.curseforge_token_or_err()?)
-            .send()
-            .await
-            .into_diagnostic()?;
+            .send_rate_limited(context)
+            .await?;
 
         if !response.status().is_success() {
             return Err(miette!(
@@ -59,9 +62,8 @@ impl ApiRequest<Vec<GameVersion>> for GameVersions {
             .client
             .get(url)
             .header(AUTH_KEY, context.secrets.curseforge_token_or_err()?)
-            .send()
-            .await
-            .into_diagnostic()?;
+            .send_rate_limited(context)
+            .await?;
 
         if !response.status().is_success() {
             return Err(miette!(
@@ -94,8 +96,10 @@ pub struct ProjectUploadFileResponse {
     id: u32,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn upload_asset_to_curseforge(
     context: &Context,
+    source: &dyn ReleaseSource,
     config: &Config,
     release: &Release,
     asset: &Asset,
@@ -104,7 +108,11 @@ async fn upload_asset_to_curseforge(
     game_versions: &[u32],
 ) -> Result<ProjectUploadFileResponse> {
     let metadata = ProjectUploadFileData {
-        changelog: release.body.clone().unwrap_or_default(),
+        changelog: release
+            .body
+            .as_deref()
+            .map(without_links_section)
+            .unwrap_or_default(),
         changelog_type: "markdown",
         display_name: release.name.clone(),
         parent_file_id,
@@ -119,20 +127,22 @@ async fn upload_asset_to_curseforge(
         "metadata",
         serde_json::to_string(&metadata).into_diagnostic()?,
     );
-    GetAsset(asset)
-        .attach_to_form(context, &mut form, "file".to_string())
+    source
+        .attach_to_form(context, &mut form, asset, "file".to_string())
         .await?;
 
     let url = format!("{}/projects/{}/upload-file", API_URL, settings.project_id);
-    let response = context
-        .client
-        .post(url)
-        .header(AUTH_KEY, context.secrets.curseforge_token_or_err()?)
-        .header(CONTENT_TYPE, form.content_type())
-        .body(body_with_progress(context, form.bytes()))
-        .send()
-        .await
-        .into_diagnostic()?;
+    let token = context.secrets.curseforge_token_or_err()?;
+    let body = form.bytes();
+    let response = send_rate_limited_with(context, || {
+        context
+            .client
+            .post(&url)
+            .header(AUTH_KEY, token)
+            .header(CONTENT_TYPE, form.content_type())
+            .body(body_with_progress(context, body.clone()))
+    })
+    .await?;
 
     if !response.status().is_success() {
         return Err(miette!(
@@ -151,11 +161,12 @@ async fn upload_asset_to_curseforge(
 
 pub async fn upload_to_curseforge(
     context: &Context,
+    source: &dyn ReleaseSource,
     config: &Config,
     project: &Project,
     release: &Release,
     settings: &CurseForgeSettings,
-) -> Result<()> {
+) -> Result<Publication> {
     let allowed_game_version_types: Vec<u32> = GameVersionTypes
         .request(context)
         .await?
@@ -172,7 +183,9 @@ pub async fn upload_to_curseforge(
     let mut game_versions = project.get_game_versions(config)?;
 
     for loader in project.get_loaders(config)? {
-        game_versions.push(loader.curseforge_name().to_string());
+        if let Some(name) = loader.curseforge_name() {
+            game_versions.push(name.to_string());
+        }
     }
 
     let game_versions: Vec<u32> = GameVersions
@@ -184,8 +197,7 @@ pub async fn upload_to_curseforge(
         .map(|version| version.id)
         .collect();
 
-    let file_regex = project.get_regex(config)?;
-    let assets = release.get_assets(&file_regex);
+    let assets = project.get_assets(config, release)?;
 
     let bar = context
         .progress
@@ -197,6 +209,7 @@ pub async fn upload_to_curseforge(
     let tail: Vec<_> = assets.iter().skip(1).collect();
     let primary_id = upload_asset_to_curseforge(
         context,
+        source,
         config,
         release,
         head,
@@ -211,6 +224,7 @@ pub async fn upload_to_curseforge(
         bar.inc(1);
         upload_asset_to_curseforge(
             context,
+            source,
             config,
             release,
             asset,
@@ -224,18 +238,25 @@ pub async fn upload_to_curseforge(
     bar.finish_and_clear();
 
     // Let's print a link to the version if we have the slug.
-    if let Some(slug) = &settings.slug {
+    let url = settings
+        .get_url()
+        .map(|url| format!("{}/files/{}", url, primary_id));
+    if let Some(url) = &url {
         context
             .progress
-            .println(format!(
-                "{} https://curseforge.com/minecraft/mc-mods/{}/files/{}",
-                console::style("Link:").bold().blue(),
-                slug,
-                primary_id
-            ))
+            .println(format!("{} {}", console::style("Link:").bold().blue(), url))
             .into_diagnostic()
             .wrap_err("Could not print link to release")?;
     }
 
-    Ok(())
+    Ok(Publication {
+        platform: Platform::CurseForge,
+        project: settings
+            .slug
+            .as_ref()
+            .unwrap_or(&settings.project_id)
+            .clone(),
+        id: primary_id.to_string(),
+        url,
+    })
 }
@@ -0,0 +1,168 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashSet;
+
+use bytes::Bytes;
+use miette::{miette, IntoDiagnostic, Result};
+use sha1::{Digest, Sha1};
+
+use crate::config::{Config, GalleryImageSettings};
+use crate::error::MuError;
+use crate::modrinth::{AddGalleryImage, GalleryImage, GetProject};
+use crate::requests::{bytes_with_progress, ApiRequest, Context, RateLimitedSend};
+
+/// Uploads the configured gallery images to the Modrinth projects,
+/// skipping the images whose content is already in the gallery.
+pub async fn upload_gallery(context: &Context, config: &Config) -> Result<()> {
+    let mut found = false;
+
+    for project in config.get_projects() {
+        let Some(settings) = project.get_modrinth(config) else {
+            continue;
+        };
+        let Some(images) = &settings.gallery else {
+            continue;
+        };
+        found = true;
+
+        let existing = GetProject {
+            api_url: settings.api_url(),
+            id_or_slug: &settings.project_id,
+        }
+        .request(context)
+        .await?;
+
+        let mut gallery = GalleryHashes::new(&existing.gallery);
+        // The hashes of the images uploaded in this run.
+        let mut uploaded = HashSet::new();
+
+        for image in images {
+            let bytes = read_image(image).await?;
+            let hash = hex::encode(Sha1::digest(&bytes));
+
+            if !uploaded.insert(hash.clone())
+                || gallery
+                    .contains(context, &hash, image.title.as_deref())
+                    .await?
+            {
+                context
+                    .progress
+                    .println(format!(
+                        "Skipping {}: already in the gallery of Modrinth project {}",
+                        image.file.display(),
+                        settings.project_id
+                    ))
+                    .into_diagnostic()?;
+                continue;
+            }
+
+            let ext = image
+                .file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .ok_or_else(|| {
+                    miette!(
+                        "Gallery image {} has no file extension",
+                        image.file.display()
+                    )
+                })?;
+
+            AddGalleryImage {
+                api_url: settings.api_url(),
+                project_id: &settings.project_id,
+                ext,
+                featured: image.featured,
+                title: image.title.as_deref(),
+                description: image.description.as_deref(),
+                image: bytes,
+            }
+            .request(context)
+            .await?;
+            context
+                .progress
+                .println(format!(
+                    "Added {} to the gallery of Modrinth project {}",
+                    image.file.display(),
+                    settings.project_id
+                ))
+                .into_diagnostic()?;
+        }
+    }
+
+    if !found {
+        return Err(miette!("No Modrinth project has gallery images"));
+    }
+
+    Ok(())
+}
+
+/// The SHA-1 hashes of the original files of the images in a gallery,
+/// downloaded only when needed.
+struct GalleryHashes<'a> {
+    images: &'a [GalleryImage],
+    hashes: Vec<Option<String>>,
+}
+
+impl<'a> GalleryHashes<'a> {
+    fn new(images: &'a [GalleryImage]) -> Self {
+        GalleryHashes {
+            images,
+            hashes: vec![None; images.len()],
+        }
+    }
+
+    /// Checks whether the gallery has an image with the hash. The images with
+    /// the same title are checked first, so that usually not every image is downloaded.
+    async fn contains(
+        &mut self,
+        context: &Context,
+        hash: &str,
+        title: Option<&str>,
+    ) -> Result<bool> {
+        let mut order: Vec<usize> = (0..self.images.len()).collect();
+        order.sort_by_key(|&i| title.is_none() || self.images[i].title.as_deref() != title);
+
+        for i in order {
+            if self.hashes[i].is_none() {
+                self.hashes[i] = Some(download_hash(context, &self.images[i]).await?);
+            }
+
+            if self.hashes[i].as_deref() == Some(hash) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// Downloads the original file of a gallery image and hashes it.
+async fn download_hash(context: &Context, image: &GalleryImage) -> Result<String> {
+    let response = context
+        .client
+        .get(image.raw_url.as_ref().unwrap_or(&image.url))
+        .send_rate_limited(context)
+        .await?
+        .error_for_status()
+        .into_diagnostic()?;
+    let bytes = bytes_with_progress(context, response).await?;
+    Ok(hex::encode(Sha1::digest(&bytes)))
+}
+
+async fn read_image(image: &GalleryImageSettings) -> Result<Bytes> {
+    tokio::fs::read(&image.file)
+        .await
+        .map(Bytes::from)
+        .map_err(|err| {
+            MuError::new(format!(
+                "Could not read gallery image {}",
+                image.file.display()
+            ))
+            .cause(err)
+            .to_report()
+        })
+}
@@ -0,0 +1,57 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::Deserialize;
+
+use crate::source::{Asset, Release};
+
+#[derive(Deserialize, Debug)]
+pub struct GiteaRelease {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub prerelease: bool,
+    pub draft: bool,
+    pub published_at: Option<String>,
+    pub assets: Vec<Attachment>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Attachment {
+    pub id: u64,
+    pub name: String,
+    pub size: u64,
+    pub browser_download_url: String,
+}
+
+impl GiteaRelease {
+    /// Converts this release into the common [`Release`] format.
+    pub fn into_release(self) -> Release {
+        let assets = self
+            .assets
+            .into_iter()
+            .map(|attachment| Asset {
+                id: Some(attachment.id),
+                url: attachment.browser_download_url,
+                name: attachment.name,
+                size: Some(attachment.size),
+                digest: None,
+            })
+            .collect();
+
+        Release {
+            id: Some(self.id),
+            tag_name: self.tag_name,
+            name: self.name,
+            body: self.body,
+            prerelease: self.prerelease,
+            draft: self.draft,
+            published_at: self.published_at,
+            assets,
+        }
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use async_trait::async_trait;
+use miette::{miette, IntoDiagnostic, Result};
+use reqwest::RequestBuilder;
+
+use crate::gitea::GiteaRelease;
+use crate::github::Repo;
+use crate::requests::{
+    bytes_with_progress, encode_path_segment, json_with_progress, ApiRequest, Context,
+};
+use crate::source::Asset;
+
+pub const DEFAULT_INSTANCE_URL: &str = "https://codeberg.org";
+const AUTH_KEY: &str = "Authorization";
+
+pub struct GetGiteaRelease<'a> {
+    /// The base URL of the Gitea or Forgejo instance, such as `https://codeberg.org`.
+    pub instance_url: &'a str,
+    pub repo: &'a Repo,
+    pub tag: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<GiteaRelease> for GetGiteaRelease<'_> {
+    async fn request(&self, context: &Context) -> Result<GiteaRelease> {
+        let url = format!(
+            "{}/api/v1/repos/{}/{}/releases/tags/{}",
+            self.instance_url.trim_end_matches('/'),
+            encode_path_segment(&self.repo.owner),
+            encode_path_segment(&self.repo.name),
+            encode_path_segment(self.tag)
+        );
+        let response = with_auth(context, context.client.get(url))
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get release {}/{}@{} from Gitea: {}\n{}",
+                self.repo.owner,
+                self.repo.name,
+                self.tag,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+pub struct ListGiteaReleases<'a> {
+    /// The base URL of the Gitea or Forgejo instance, such as `https://codeberg.org`.
+    pub instance_url: &'a str,
+    pub repo: &'a Repo,
+    pub page: u32,
+}
+
+/// The default maximum page size of Gitea instances.
+pub const RELEASES_PER_PAGE: usize = 50;
+
+#[async_trait]
+impl ApiRequest<Vec<GiteaRelease>> for ListGiteaReleases<'_> {
+    async fn request(&self, context: &Context) -> Result<Vec<GiteaRelease>> {
+        let url = format!(
+            "{}/api/v1/repos/{}/{}/releases?limit={}&page={}",
+            self.instance_url.trim_end_matches('/'),
+            encode_path_segment(&self.repo.owner),
+            encode_path_segment(&self.repo.name),
+            RELEASES_PER_PAGE,
+            self.page
+        );
+        let response = with_auth(context, context.client.get(url))
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not list releases of {}/{} from Gitea: {}\n{}",
+                self.repo.owner,
+                self.repo.name,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+/// Lists all releases of a repository, newest first.
+pub async fn list_all_gitea_releases(
+    context: &Context,
+    instance_url: &str,
+    repo: &Repo,
+) -> Result<Vec<GiteaRelease>> {
+    let mut releases = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let batch = ListGiteaReleases {
+            instance_url,
+            repo,
+            page,
+        }
+        .request(context)
+        .await?;
+        // Instances can lower the maximum page size, so only an empty page ends the list.
+        if batch.is_empty() {
+            break;
+        }
+
+        releases.extend(batch);
+        page += 1;
+    }
+
+    Ok(releases)
+}
+
+/// Downloads a Gitea release attachment.
+pub struct GetGiteaAsset<'a>(pub &'a Asset);
+
+#[async_trait]
+impl ApiRequest<bytes::Bytes> for GetGiteaAsset<'_> {
+    async fn request(&self, context: &Context) -> Result<bytes::Bytes> {
+        let response = with_auth(context, context.client.get(&self.0.url))
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get asset file from Gitea at {}: {}\n{}",
+                self.0.url,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        bytes_with_progress(context, response).await
+    }
+}
+
+// The token is optional because public repositories can be read without one.
+fn with_auth(context: &Context, builder: RequestBuilder) -> RequestBuilder {
+    if let Some(token) = &context.secrets.gitea_token {
+        builder.header(AUTH_KEY, format!("token {}", token))
+    } else {
+        builder
+    }
+}
@@ -0,0 +1,51 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::Result;
+
+use crate::gitea::{list_all_gitea_releases, GetGiteaAsset, GetGiteaRelease};
+use crate::github::Repo;
+use crate::requests::{ApiRequest, Context};
+use crate::source::{Asset, Release, ReleaseSource};
+
+/// Reads releases from a Gitea or Forgejo repository.
+pub struct GiteaSource {
+    /// The base URL of the Gitea or Forgejo instance, such as `https://codeberg.org`.
+    pub instance_url: String,
+    pub repo: Repo,
+}
+
+#[async_trait]
+impl ReleaseSource for GiteaSource {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    async fn get_release(&self, context: &Context, tag: &str) -> Result<Release> {
+        let release = GetGiteaRelease {
+            instance_url: &self.instance_url,
+            repo: &self.repo,
+            tag,
+        }
+        .request(context)
+        .await?;
+        Ok(release.into_release())
+    }
+
+    async fn list_releases(&self, context: &Context) -> Result<Vec<Release>> {
+        let releases = list_all_gitea_releases(context, &self.instance_url, &self.repo).await?;
+        Ok(releases
+            .into_iter()
+            .map(|release| release.into_release())
+            .collect())
+    }
+
+    async fn get_asset_bytes(&self, context: &Context, asset: &Asset) -> Result<Bytes> {
+        GetGiteaAsset(asset).request(context).await
+    }
+}
@@ -5,38 +5,6 @@
  */
 
 use miette::{miette, Result};
-use regex::Regex;
-use serde::Deserialize;
-
-#[derive(Deserialize, Debug)]
-pub struct Release {
-    pub tag_name: String,
-    pub name: Option<String>,
-    pub body: Option<String>,
-    pub prerelease: bool,
-    pub assets: Vec<Asset>,
-}
-
-impl Release {
-    pub fn get_assets(&self, file_regex: &Option<Regex>) -> Vec<&Asset> {
-        self.assets
-            .iter()
-            .filter(|asset| {
-                if let Some(regex) = &file_regex {
-                    regex.is_match(asset.name.as_str())
-                } else {
-                    true
-                }
-            })
-            .collect()
-    }
-}
-
-#[derive(Deserialize, Debug)]
-pub struct Asset {
-    pub url: String,
-    pub name: String,
-}
 
 pub struct Repo {
     pub owner: String,
@@ -53,7 +21,7 @@ impl Repo {
 
         if parts.len() != 2 {
             return Err(miette!(
-                "Expected GitHub repository name in the format 'owner/repo', found {}",
+                "Expected repository name in the format 'owner/repo', found {}",
                 str
             ));
         }
@@ -0,0 +1,193 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use miette::{miette, Result};
+
+use crate::github::{Repo, UpdateReleaseBody};
+use crate::publish::Publication;
+use crate::requests::{ApiRequest, Context};
+use crate::source::Release;
+
+const LINKS_START: &str = "<!-- mirror_upload:links -->";
+const LINKS_END: &str = "<!-- /mirror_upload:links -->";
+
+/// Adds or replaces the "Also available on" section of a GitHub release body
+/// with links to the publications.
+pub async fn write_release_links(
+    context: &Context,
+    repo: &Repo,
+    release: &Release,
+    publications: &[Publication],
+) -> Result<()> {
+    let release_id = release
+        .id
+        .ok_or_else(|| miette!("Release {} has no GitHub ID", release.tag_name))?;
+    let old_body = release.body.clone().unwrap_or_default();
+    let body = with_links_section(&old_body, publications);
+
+    if body != old_body {
+        UpdateReleaseBody {
+            repo,
+            release_id,
+            body: &body,
+        }
+        .request(context)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Replaces the links section in a release body, or appends it if missing.
+/// Publications without a link are left out, and the section is removed
+/// if none of them has a link.
+pub fn with_links_section(body: &str, publications: &[Publication]) -> String {
+    let links: Vec<String> = publications
+        .iter()
+        .filter_map(|publication| {
+            let url = publication.url.as_ref()?;
+            Some(format!(
+                "- [{} ({})]({})",
+                publication.platform.name(),
+                publication.project,
+                url
+            ))
+        })
+        .collect();
+
+    if links.is_empty() {
+        return without_links_section(body);
+    }
+
+    let section = format!(
+        "{}\n### Also available on\n\n{}\n{}",
+        LINKS_START,
+        links.join("\n"),
+        LINKS_END
+    );
+
+    if let Some((start, end)) = find_links_section(body) {
+        return format!("{}{}{}", &body[..start], section, &body[end..]);
+    }
+
+    if body.trim().is_empty() {
+        section
+    } else {
+        format!("{}\n\n{}", body.trim_end(), section)
+    }
+}
+
+/// Removes the links section from a release body, so that
+/// it isn't mirrored into changelogs.
+pub fn without_links_section(body: &str) -> String {
+    match find_links_section(body) {
+        Some((start, end)) => {
+            let before = body[..start].trim_end();
+            let after = body[end..].trim_start();
+
+            if after.is_empty() {
+                before.to_string()
+            } else if before.is_empty() {
+                after.to_string()
+            } else {
+                format!("{}\n\n{}", before, after)
+            }
+        }
+        None => body.to_string(),
+    }
+}
+
+/// Gets the URLs of the links in the links section of a release body.
+pub fn get_release_links(body: &str) -> Vec<String> {
+    let Some((start, end)) = find_links_section(body) else {
+        return vec![];
+    };
+
+    body[start..end]
+        .lines()
+        .filter_map(|line| {
+            let url = line.trim().strip_prefix("- [")?.rsplit_once("](")?.1;
+            Some(url.strip_suffix(')')?.to_string())
+        })
+        .collect()
+}
+
+/// Finds the byte range of the links section in a release body.
+fn find_links_section(body: &str) -> Option<(usize, usize)> {
+    let start = body.find(LINKS_START)?;
+    let end = body[start..].find(LINKS_END)?;
+    Some((start, start + end + LINKS_END.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::publish::Platform;
+
+    fn publication(platform: Platform, url: Option<&str>) -> Publication {
+        Publication {
+            platform,
+            project: "example".to_string(),
+            id: "1".to_string(),
+            url: url.map(str::to_string),
+        }
+    }
+
+    fn modrinth(url: &str) -> Vec<Publication> {
+        vec![publication(Platform::Modrinth, Some(url))]
+    }
+
+    #[test]
+    fn appends_section() {
+        let body = with_links_section("Changelog\n", &modrinth("https://modrinth.com/a"));
+        assert_eq!(
+            body,
+            "Changelog\n\n<!-- mirror_upload:links -->\n### Also available on\n\n\
+             - [Modrinth (example)](https://modrinth.com/a)\n<!-- /mirror_upload:links -->"
+        );
+        assert_eq!(get_release_links(&body), ["https://modrinth.com/a"]);
+    }
+
+    #[test]
+    fn replaces_section_on_rerun() {
+        let body = with_links_section("Changelog", &modrinth("https://modrinth.com/a"));
+        let body = format!("{}\n\nFooter", body);
+        let publications = vec![
+            publication(Platform::Modrinth, Some("https://modrinth.com/b")),
+            publication(Platform::CurseForge, None),
+        ];
+        let rerun = with_links_section(&body, &publications);
+
+        assert_eq!(rerun.matches(LINKS_START).count(), 1);
+        assert!(rerun.starts_with("Changelog\n\n"));
+        assert!(rerun.ends_with("\n\nFooter"));
+        assert_eq!(get_release_links(&rerun), ["https://modrinth.com/b"]);
+        assert_eq!(with_links_section(&rerun, &publications), rerun);
+    }
+
+    #[test]
+    fn removes_section_without_links() {
+        let body = with_links_section("Changelog", &modrinth("https://modrinth.com/a"));
+        let publications = vec![publication(Platform::CurseForge, None)];
+        assert_eq!(with_links_section(&body, &publications), "Changelog");
+        assert_eq!(with_links_section("Changelog", &[]), "Changelog");
+    }
+
+    #[test]
+    fn strips_section() {
+        let section = with_links_section("", &modrinth("https://modrinth.com/a"));
+        assert_eq!(without_links_section(&section), "");
+        assert_eq!(
+            without_links_section(&format!("Before\n\n{}\n\nAfter", section)),
+            "Before\n\nAfter"
+        );
+        assert_eq!(
+            without_links_section(&format!("{}\nAfter", section)),
+            "After"
+        );
+        assert_eq!(without_links_section("No links"), "No links");
+    }
+}
@@ -0,0 +1,214 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use regex::{Captures, Regex};
+
+use crate::github::Repo;
+
+/// Rewrites the relative links and images of a Markdown document in a GitHub
+/// repository into absolute URLs, so that the document can be shown elsewhere.
+/// Code blocks and code spans are left as is.
+///
+/// `base_dir` is the directory of the document relative to the repository root,
+/// and `git_ref` is the branch, tag or commit the URLs point to.
+pub fn absolute_links(markdown: &str, repo: &Repo, git_ref: &str, base_dir: &str) -> String {
+    let rewriter = LinkRewriter {
+        markdown_link: Regex::new(r#"(!?)\[([^\]]*)\]\(([^)\s]+)([^)]*)\)"#).unwrap(),
+        html_attribute: Regex::new(r#"\b(src|href)="([^"]+)""#).unwrap(),
+        code_span: Regex::new(r"``.+?``|`[^`]+`").unwrap(),
+        scheme: Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*:").unwrap(),
+        repo,
+        git_ref,
+        base_dir,
+    };
+    let mut result = String::with_capacity(markdown.len());
+    let mut text = String::new();
+    let mut fence: Option<&str> = None;
+
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            result.push_str(line);
+            continue;
+        }
+
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            result.push_str(&rewriter.rewrite(&text));
+            text.clear();
+            result.push_str(line);
+            fence = Some(marker);
+            continue;
+        }
+
+        text.push_str(line);
+    }
+
+    result.push_str(&rewriter.rewrite(&text));
+    result
+}
+
+struct LinkRewriter<'a> {
+    markdown_link: Regex,
+    html_attribute: Regex,
+    code_span: Regex,
+    scheme: Regex,
+    repo: &'a Repo,
+    git_ref: &'a str,
+    base_dir: &'a str,
+}
+
+impl LinkRewriter<'_> {
+    /// Rewrites the links of Markdown text outside of its code spans.
+    fn rewrite(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut end = 0;
+
+        for code in self.code_span.find_iter(text) {
+            result.push_str(&self.rewrite_links(&text[end..code.start()]));
+            result.push_str(code.as_str());
+            end = code.end();
+        }
+
+        result.push_str(&self.rewrite_links(&text[end..]));
+        result
+    }
+
+    fn rewrite_links(&self, text: &str) -> String {
+        let result = self.markdown_link.replace_all(text, |captures: &Captures| {
+            let is_image = !captures[1].is_empty();
+            format!(
+                "{}[{}]({}{})",
+                &captures[1],
+                &captures[2],
+                self.absolute_url(&captures[3], is_image),
+                &captures[4]
+            )
+        });
+        let result = self
+            .html_attribute
+            .replace_all(&result, |captures: &Captures| {
+                let is_image = &captures[1] == "src";
+                format!(
+                    r#"{}="{}""#,
+                    &captures[1],
+                    self.absolute_url(&captures[2], is_image)
+                )
+            });
+        result.into_owned()
+    }
+
+    fn absolute_url(&self, url: &str, is_image: bool) -> String {
+        // Keep anchors, protocol-relative URLs and URLs with a scheme,
+        // such as `https:`, `mailto:`, `data:` or `tel:`.
+        if url.starts_with('#') || url.starts_with("//") || self.scheme.is_match(url) {
+            return url.to_string();
+        }
+
+        let path = if let Some(path) = url.strip_prefix('/') {
+            normalize_path(path)
+        } else {
+            normalize_path(&format!("{}/{}", self.base_dir, url))
+        };
+        let repo = self.repo;
+
+        if is_image {
+            format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                repo.owner, repo.name, self.git_ref, path
+            )
+        } else {
+            format!(
+                "https://github.com/{}/{}/blob/{}/{}",
+                repo.owner, repo.name, self.git_ref, path
+            )
+        }
+    }
+}
+
+/// Resolves the `.` and `..` segments of a relative path.
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(markdown: &str) -> String {
+        let repo = Repo {
+            owner: "owner".to_string(),
+            name: "repo".to_string(),
+        };
+        absolute_links(markdown, &repo, "main", "docs")
+    }
+
+    #[test]
+    fn rewrites_links_and_images() {
+        assert_eq!(
+            rewrite("[Guide](guide.md) ![Logo](img/logo.png \"Logo\")"),
+            "[Guide](https://github.com/owner/repo/blob/main/docs/guide.md) \
+             ![Logo](https://raw.githubusercontent.com/owner/repo/main/docs/img/logo.png \"Logo\")"
+        );
+        assert_eq!(
+            rewrite(r#"<img src="logo.png"> <a href="/LICENSE">"#),
+            r#"<img src="https://raw.githubusercontent.com/owner/repo/main/docs/logo.png"> <a href="https://github.com/owner/repo/blob/main/LICENSE">"#
+        );
+    }
+
+    #[test]
+    fn resolves_parent_directories() {
+        assert_eq!(
+            rewrite("[License](../LICENSE) [Readme](./../a/../README.md)"),
+            "[License](https://github.com/owner/repo/blob/main/LICENSE) \
+             [Readme](https://github.com/owner/repo/blob/main/README.md)"
+        );
+    }
+
+    #[test]
+    fn keeps_urls_with_schemes_and_anchors() {
+        let markdown = "[Site](https://example.com) [Mail](mailto:a@example.com) \
+                        [Data](data:image/png;base64,AA) [Cdn](//cdn.example.com/a.png) \
+                        [Section](#usage)";
+        assert_eq!(rewrite(markdown), markdown);
+    }
+
+    #[test]
+    fn skips_code_spans() {
+        assert_eq!(
+            rewrite("`[a](a.md)` ``[b](`b`.md)`` [c](c.md)"),
+            "`[a](a.md)` ``[b](`b`.md)`` [c](https://github.com/owner/repo/blob/main/docs/c.md)"
+        );
+    }
+
+    #[test]
+    fn skips_fenced_code_blocks() {
+        let markdown = "```md\n[a](a.md)\n```\n~~~\n[b](b.md)\n~~~\n[c](c.md)\n";
+        assert_eq!(
+            rewrite(markdown),
+            "```md\n[a](a.md)\n```\n~~~\n[b](b.md)\n~~~\n\
+             [c](https://github.com/owner/repo/blob/main/docs/c.md)\n"
+        );
+    }
+}
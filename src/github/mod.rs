@@ -5,7 +5,13 @@
  */
 
 mod data;
+mod links;
+mod markdown;
 mod requests;
+mod source;
 
 pub use data::*;
+pub use links::*;
+pub use markdown::*;
 pub use requests::*;
+pub use source::*;
@@ -5,11 +5,17 @@
  */
 
 use async_trait::async_trait;
+
 use miette::{miette, IntoDiagnostic, Result};
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use serde::Serialize;
 
-use crate::github::{Asset, Release};
-use crate::requests::multipart::Form;
-use crate::requests::{bytes_with_progress, json_with_progress, ApiRequest, Context};
+use crate::github::Repo;
+use crate::requests::{
+    bytes_with_progress, is_rate_limited, json_with_progress, ApiRequest, Context, RateLimitedSend,
+};
+use crate::source::{Asset, Release};
 
 const API_URL: &str = "https://api.github.com";
 const API_VERSION_KEY: &str = "X-GitHub-Api-Version";
@@ -55,21 +61,184 @@ impl ApiRequest<Release> for GetReleaseByTagName {
     }
 }
 
-pub struct GetAsset<'a>(pub &'a Asset);
+/// Replaces the body of a release.
+pub struct UpdateReleaseBody<'a> {
+    pub repo: &'a Repo,
+    pub release_id: u64,
+    pub body: &'a str,
+}
+
+#[derive(Serialize)]
+struct UpdateReleaseBodyData<'a> {
+    body: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<Release> for UpdateReleaseBody<'_> {
+    async fn request(&self, context: &Context) -> Result<Release> {
+        let url = format!(
+            "{}/repos/{}/{}/releases/{}",
+            API_URL, self.repo.owner, self.repo.name, self.release_id
+        );
+        let response = context
+            .client
+            .patch(url)
+            .header("Accept", JSON_CONTENT_TYPE)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .header(API_VERSION_KEY, API_VERSION)
+            .json(&UpdateReleaseBodyData { body: self.body })
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not update release {} of {}/{} on GitHub: {}\n{}",
+                self.release_id,
+                self.repo.owner,
+                self.repo.name,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
 
-impl GetAsset<'_> {
-    pub async fn attach_to_form(
-        &self,
-        context: &Context,
-        form: &mut Form,
-        field_name: String,
-    ) -> Result<()> {
-        let asset_bytes = self.request(context).await?;
-        form.file(field_name, &self.0.name, asset_bytes);
-        Ok(())
+        json_with_progress(context, response).await
     }
 }
 
+/// Lists a single page of releases, newest first.
+pub struct ListReleases {
+    pub owner: String,
+    pub repo: String,
+    pub page: u32,
+}
+
+pub const RELEASES_PER_PAGE: usize = 100;
+
+#[async_trait]
+impl ApiRequest<Vec<Release>> for ListReleases {
+    async fn request(&self, context: &Context) -> Result<Vec<Release>> {
+        let url = format!(
+            "{}/repos/{}/{}/releases?per_page={}&page={}",
+            API_URL, self.owner, self.repo, RELEASES_PER_PAGE, self.page
+        );
+        let response = context
+            .client
+            .get(url)
+            .header("Accept", JSON_CONTENT_TYPE)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .header(API_VERSION_KEY, API_VERSION)
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not list releases of {}/{} from GitHub: {}\n{}",
+                self.owner,
+                self.repo,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+/// Polls the newest page of releases with a conditional request,
+/// so that unchanged releases don't count against the rate limit.
+pub struct PollReleases<'a> {
+    pub repo: &'a Repo,
+    /// The ETag of the previous poll.
+    pub etag: Option<&'a str>,
+}
+
+pub struct ReleasesPoll {
+    /// The releases, newest first, or `None` if nothing has changed since the previous poll
+    /// or if the rate limit has been exceeded. The next poll waits for the rate limit to reset.
+    pub releases: Option<Vec<Release>>,
+    pub etag: Option<String>,
+}
+
+#[async_trait]
+impl ApiRequest<ReleasesPoll> for PollReleases<'_> {
+    async fn request(&self, context: &Context) -> Result<ReleasesPoll> {
+        let url = format!(
+            "{}/repos/{}/{}/releases?per_page={}",
+            API_URL, self.repo.owner, self.repo.name, RELEASES_PER_PAGE
+        );
+        let mut builder = context
+            .client
+            .get(url)
+            .header("Accept", JSON_CONTENT_TYPE)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .header(API_VERSION_KEY, API_VERSION);
+
+        if let Some(etag) = self.etag {
+            builder = builder.header(IF_NONE_MATCH, etag);
+        }
+
+        let response = builder.send_rate_limited(context).await?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        if response.status() == StatusCode::NOT_MODIFIED || is_rate_limited(&response) {
+            return Ok(ReleasesPoll {
+                releases: None,
+                etag: self.etag.map(|etag| etag.to_string()),
+            });
+        }
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not poll releases of {}/{} from GitHub: {}\n{}",
+                self.repo.owner,
+                self.repo.name,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(ReleasesPoll {
+            releases: Some(json_with_progress(context, response).await?),
+            etag,
+        })
+    }
+}
+
+/// Lists all releases of a repository, newest first.
+pub async fn list_all_releases(context: &Context, repo: &Repo) -> Result<Vec<Release>> {
+    let mut releases = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let batch = ListReleases {
+            owner: repo.owner.clone(),
+            repo: repo.name.clone(),
+            page,
+        }
+        .request(context)
+        .await?;
+        let done = batch.len() < RELEASES_PER_PAGE;
+        releases.extend(batch);
+
+        if done {
+            break;
+        }
+
+        page += 1;
+    }
+
+    Ok(releases)
+}
+
+/// Downloads a GitHub release asset.
+pub struct GetAsset<'a>(pub &'a Asset);
+
 #[async_trait]
 impl ApiRequest<bytes::Bytes> for GetAsset<'_> {
     async fn request(&self, context: &Context) -> Result<bytes::Bytes> {
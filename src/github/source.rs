@@ -0,0 +1,43 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::Result;
+
+use crate::github::{list_all_releases, GetAsset, GetReleaseByTagName, Repo};
+use crate::requests::{ApiRequest, Context};
+use crate::source::{Asset, Release, ReleaseSource};
+
+/// Reads releases from a GitHub repository.
+pub struct GitHubSource {
+    pub repo: Repo,
+}
+
+#[async_trait]
+impl ReleaseSource for GitHubSource {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    async fn get_release(&self, context: &Context, tag: &str) -> Result<Release> {
+        GetReleaseByTagName {
+            owner: self.repo.owner.clone(),
+            repo: self.repo.name.clone(),
+            tag: tag.to_string(),
+        }
+        .request(context)
+        .await
+    }
+
+    async fn list_releases(&self, context: &Context) -> Result<Vec<Release>> {
+        list_all_releases(context, &self.repo).await
+    }
+
+    async fn get_asset_bytes(&self, context: &Context, asset: &Asset) -> Result<Bytes> {
+        GetAsset(asset).request(context).await
+    }
+}
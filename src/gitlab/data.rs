@@ -0,0 +1,65 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use serde::Deserialize;
+
+use crate::source::{Asset, Release};
+
+#[derive(Deserialize, Debug)]
+pub struct GitLabRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub released_at: Option<String>,
+    pub assets: GitLabAssets,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitLabAssets {
+    pub links: Vec<ReleaseLink>,
+}
+
+/// A release link. Package files only become assets
+/// if they are linked to the release.
+#[derive(Deserialize, Debug)]
+pub struct ReleaseLink {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+    pub direct_asset_url: Option<String>,
+}
+
+impl GitLabRelease {
+    /// Converts this release into the common [`Release`] format.
+    ///
+    /// GitLab has no pre-release flag, so the release is never a pre-release.
+    /// The `release_level` setting can be used to publish it as a beta or alpha instead.
+    pub fn into_release(self) -> Release {
+        let assets = self
+            .assets
+            .links
+            .into_iter()
+            .map(|link| Asset {
+                id: Some(link.id),
+                url: link.direct_asset_url.unwrap_or(link.url),
+                name: link.name,
+                size: None,
+                digest: None,
+            })
+            .collect();
+
+        Release {
+            id: None,
+            tag_name: self.tag_name,
+            name: self.name,
+            body: self.description,
+            prerelease: false,
+            draft: false,
+            published_at: self.released_at,
+            assets,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use async_trait::async_trait;
+use miette::{miette, IntoDiagnostic, Result};
+use reqwest::{RequestBuilder, Url};
+
+use crate::gitlab::GitLabRelease;
+use crate::requests::{
+    bytes_with_progress, encode_path_segment, json_with_progress, ApiRequest, Context,
+};
+use crate::source::Asset;
+
+pub const DEFAULT_INSTANCE_URL: &str = "https://gitlab.com";
+pub struct GetGitLabRelease<'a> {
+    /// The base URL of the GitLab instance, such as `https://gitlab.com`.
+    pub instance_url: &'a str,
+    /// The full path of the project (format: "group/project").
+    pub project: &'a str,
+    pub tag: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<GitLabRelease> for GetGitLabRelease<'_> {
+    async fn request(&self, context: &Context) -> Result<GitLabRelease> {
+        let url = format!(
+            "{}/api/v4/projects/{}/releases/{}",
+            self.instance_url.trim_end_matches('/'),
+            encode_path_segment(self.project),
+            encode_path_segment(self.tag)
+        );
+        let response = get(context, self.instance_url, &url)
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get release {}@{} from GitLab: {}\n{}",
+                self.project,
+                self.tag,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+pub struct ListGitLabReleases<'a> {
+    /// The base URL of the GitLab instance, such as `https://gitlab.com`.
+    pub instance_url: &'a str,
+    /// The full path of the project (format: "group/project").
+    pub project: &'a str,
+    pub page: u32,
+}
+
+pub const RELEASES_PER_PAGE: usize = 100;
+
+#[async_trait]
+impl ApiRequest<Vec<GitLabRelease>> for ListGitLabReleases<'_> {
+    async fn request(&self, context: &Context) -> Result<Vec<GitLabRelease>> {
+        let url = format!(
+            "{}/api/v4/projects/{}/releases?per_page={}&page={}",
+            self.instance_url.trim_end_matches('/'),
+            encode_path_segment(self.project),
+            RELEASES_PER_PAGE,
+            self.page
+        );
+        let response = get(context, self.instance_url, &url)
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not list releases of {} from GitLab: {}\n{}",
+                self.project,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+/// Lists all releases of a project, newest first.
+pub async fn list_all_gitlab_releases(
+    context: &Context,
+    instance_url: &str,
+    project: &str,
+) -> Result<Vec<GitLabRelease>> {
+    let mut releases = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let batch = ListGitLabReleases {
+            instance_url,
+            project,
+            page,
+        }
+        .request(context)
+        .await?;
+        let done = batch.len() < RELEASES_PER_PAGE;
+        releases.extend(batch);
+
+        if done {
+            break;
+        }
+
+        page += 1;
+    }
+
+    Ok(releases)
+}
+
+/// Downloads a GitLab release link.
+pub struct GetGitLabAsset<'a> {
+    /// The base URL of the GitLab instance, such as `https://gitlab.com`.
+    pub instance_url: &'a str,
+    pub asset: &'a Asset,
+}
+
+#[async_trait]
+impl ApiRequest<bytes::Bytes> for GetGitLabAsset<'_> {
+    async fn request(&self, context: &Context) -> Result<bytes::Bytes> {
+        let response = get(context, self.instance_url, &self.asset.url)
+            .send()
+            .await
+            .into_diagnostic()?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get asset file from GitLab at {}: {}\n{}",
+                self.asset.url,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        bytes_with_progress(context, response).await
+    }
+}
+
+/// Creates a GET request, authenticated if there is a token.
+///
+/// The token is optional because public projects can be read without one.
+/// Release links can point to any host, so the token is only sent
+/// to URLs with the same scheme, host and port as the instance.
+/// Asset URLs on the instance can redirect to other hosts, so the token is sent
+/// as a bearer token, which reqwest drops on redirects to another host.
+fn get(context: &Context, instance_url: &str, url: &str) -> RequestBuilder {
+    let builder = context.client.get(url);
+    let Some(token) = &context.secrets.gitlab_token else {
+        return builder;
+    };
+
+    let same_origin = match (Url::parse(instance_url), Url::parse(url)) {
+        (Ok(instance_url), Ok(url)) => instance_url.origin() == url.origin(),
+        _ => false,
+    };
+
+    if same_origin {
+        builder.bearer_auth(token)
+    } else {
+        builder
+    }
+}
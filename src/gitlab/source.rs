@@ -0,0 +1,56 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::Result;
+
+use crate::gitlab::{list_all_gitlab_releases, GetGitLabAsset, GetGitLabRelease};
+use crate::requests::{ApiRequest, Context};
+use crate::source::{Asset, Release, ReleaseSource};
+
+/// Reads releases from a GitLab project.
+pub struct GitLabSource {
+    /// The base URL of the GitLab instance, such as `https://gitlab.com`.
+    pub instance_url: String,
+    /// The full path of the project (format: "group/project").
+    pub project: String,
+}
+
+#[async_trait]
+impl ReleaseSource for GitLabSource {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    async fn get_release(&self, context: &Context, tag: &str) -> Result<Release> {
+        let release = GetGitLabRelease {
+            instance_url: &self.instance_url,
+            project: &self.project,
+            tag,
+        }
+        .request(context)
+        .await?;
+        Ok(release.into_release())
+    }
+
+    async fn list_releases(&self, context: &Context) -> Result<Vec<Release>> {
+        let releases = list_all_gitlab_releases(context, &self.instance_url, &self.project).await?;
+        Ok(releases
+            .into_iter()
+            .map(|release| release.into_release())
+            .collect())
+    }
+
+    async fn get_asset_bytes(&self, context: &Context, asset: &Asset) -> Result<Bytes> {
+        GetGitLabAsset {
+            instance_url: &self.instance_url,
+            asset,
+        }
+        .request(context)
+        .await
+    }
+}
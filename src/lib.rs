@@ -4,11 +4,23 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+pub mod actions;
+pub mod backfill;
 pub mod config;
 pub mod curseforge;
 pub mod error;
+pub mod gallery;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
+pub mod local;
 pub mod modrinth;
 pub mod progress;
+pub mod publish;
 pub mod requests;
+pub mod retract;
+pub mod server;
+pub mod source;
+pub mod sync;
 pub mod template;
+pub mod watch;
@@ -0,0 +1,100 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::{miette, IntoDiagnostic, Result};
+
+use crate::error::MuError;
+use crate::requests::Context;
+use crate::source::{Asset, Release, ReleaseSource};
+
+/// Reads a release from local files instead of a remote repository.
+/// The tag of the release is used as its version.
+pub struct LocalSource {
+    /// A glob pattern that matches the asset files.
+    pub files: String,
+    /// The display name of the release.
+    pub name: Option<String>,
+    /// A Markdown file containing the changelog.
+    pub changelog_file: Option<PathBuf>,
+}
+
+#[async_trait]
+impl ReleaseSource for LocalSource {
+    fn name(&self) -> &'static str {
+        "local files"
+    }
+
+    async fn get_release(&self, _context: &Context, tag: &str) -> Result<Release> {
+        let mut assets = Vec::new();
+
+        for path in glob::glob(&self.files).into_diagnostic()? {
+            let path = path.into_diagnostic()?;
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let name = path
+                .file_name()
+                .ok_or_else(|| miette!("Could not get file name of {}", path.display()))?
+                .to_string_lossy()
+                .to_string();
+            let metadata = tokio::fs::metadata(&path).await.map_err(|err| {
+                MuError::new(format!("Could not read metadata of {}", path.display()))
+                    .cause(err)
+                    .to_report()
+            })?;
+
+            assets.push(Asset {
+                id: None,
+                url: path.to_string_lossy().to_string(),
+                name,
+                size: Some(metadata.len()),
+                digest: None,
+            });
+        }
+
+        if assets.is_empty() {
+            return Err(miette!("No files matched {}", self.files));
+        }
+
+        let body = if let Some(path) = &self.changelog_file {
+            let changelog = tokio::fs::read_to_string(path).await.map_err(|err| {
+                MuError::new(format!("Could not read changelog file {}", path.display()))
+                    .cause(err)
+                    .to_report()
+            })?;
+            Some(changelog)
+        } else {
+            None
+        };
+
+        Ok(Release {
+            id: None,
+            tag_name: tag.to_string(),
+            name: self.name.clone(),
+            body,
+            prerelease: false,
+            draft: false,
+            published_at: None,
+            assets,
+        })
+    }
+
+    // The URL of a local asset is its file path.
+    async fn get_asset_bytes(&self, _context: &Context, asset: &Asset) -> Result<Bytes> {
+        let bytes = tokio::fs::read(&asset.url).await.map_err(|err| {
+            MuError::new(format!("Could not read asset file {}", asset.url))
+                .cause(err)
+                .to_report()
+        })?;
+        Ok(bytes.into())
+    }
+}
@@ -5,42 +5,161 @@
  */
 
 use std::env::VarError;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use clap::Parser;
-use indicatif::{MultiProgress, ProgressBar};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use indicatif::MultiProgress;
 use miette::{miette, IntoDiagnostic, Result, WrapErr};
 use reqwest::Client;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
-use mirror_upload::config::{Config, Project};
-use mirror_upload::curseforge::upload_to_curseforge;
+use mirror_upload::actions;
+use mirror_upload::backfill::{backfill, parse_since, parse_until, BackfillOptions};
+use mirror_upload::config::Config;
 use mirror_upload::error::MuError;
-use mirror_upload::github::{GetReleaseByTagName, Repo};
-use mirror_upload::modrinth::upload_to_modrinth;
-use mirror_upload::progress::simple_progress_spinner_style;
-use mirror_upload::requests::{ApiRequest, Context, Secrets};
+use mirror_upload::gallery::upload_gallery;
+use mirror_upload::local::LocalSource;
+use mirror_upload::publish::{mirror_release, publish_tag, Platform};
+use mirror_upload::requests::{Context, RateLimits, Secrets};
+use mirror_upload::retract::{retract, RetractMode, RetractOptions};
+use mirror_upload::server::{serve, ServerOptions};
+use mirror_upload::source::{self, AssetStore};
+use mirror_upload::sync::{sync, sync_pages, SyncOptions, SyncPageOptions};
+use mirror_upload::watch::{watch, WatchOptions};
 
 #[derive(Parser)]
-#[command(version)]
+#[command(version, args_conflicts_with_subcommands = true)]
 struct Args {
-    /// GitHub version tag
-    version_tag: String,
+    /// Version tag of the release (inferred from the workflow event in GitHub Actions)
+    version_tag: Option<String>,
     /// Config file (default: ./mirror_upload.config.toml)
-    #[arg(short, long, value_name = "FILE")]
+    #[arg(short, long, value_name = "FILE", global = true)]
     config: Option<PathBuf>,
     /// Secrets file (default: ./mirror_upload.secrets.toml)
-    #[arg(short, long, value_name = "FILE")]
+    #[arg(short, long, value_name = "FILE", global = true)]
     secrets: Option<PathBuf>,
-    /// Use secrets from the GITHUB_TOKEN and CURSEFORGE_TOKEN environment variables.
+    /// Use secrets from the GITHUB_TOKEN, CURSEFORGE_TOKEN, GITLAB_TOKEN, GITEA_TOKEN
+    /// and WEBHOOK_SECRET environment variables.
     /// This also happens when the secrets file does not exist.
-    #[arg(long)]
+    #[arg(long, global = true)]
     env_secrets: bool,
+    /// Directory for caching downloaded assets across runs
+    #[arg(long, value_name = "DIR", global = true)]
+    asset_cache: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Mirror historical GitHub releases, oldest first
+    Backfill {
+        /// Oldest tag to mirror
+        #[arg(long, value_name = "TAG")]
+        from: Option<String>,
+        /// Newest tag to mirror
+        #[arg(long, value_name = "TAG")]
+        to: Option<String>,
+        /// Earliest publication date to mirror (YYYY-MM-DD or an RFC 3339 timestamp)
+        #[arg(long, value_name = "DATE", value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+        /// Latest publication date to mirror (YYYY-MM-DD or an RFC 3339 timestamp)
+        #[arg(long, value_name = "DATE", value_parser = parse_until)]
+        until: Option<DateTime<Utc>>,
+        /// Mirror at most the N newest releases in the range
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+        /// Only mirror to these platforms (default: all configured platforms)
+        #[arg(long, value_enum)]
+        platform: Vec<Platform>,
+        /// Upload to CurseForge even though existing files can't be detected
+        #[arg(long)]
+        force: bool,
+    },
+    /// Publish local files without a GitHub release
+    Local {
+        /// Glob pattern of the files to publish, for example 'build/libs/*.jar'
+        #[arg(long, value_name = "GLOB")]
+        files: String,
+        /// Version of the release, used in place of the GitHub tag
+        #[arg(long)]
+        version: String,
+        /// Display name of the release (default: the version)
+        #[arg(long)]
+        name: Option<String>,
+        /// Markdown file containing the changelog
+        #[arg(long, value_name = "FILE")]
+        changelog_file: Option<PathBuf>,
+    },
+    /// Update the metadata of existing Modrinth versions of a release
+    Sync {
+        /// Version tag of the release
+        tag: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Update the Modrinth project pages from the repository
+    SyncPage {
+        /// Only print the changes
+        #[arg(long)]
+        dry_run: bool,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Upload new gallery images to the Modrinth projects
+    Gallery,
+    /// Remove the published versions of a release from every project
+    Retract {
+        /// Version tag of the release
+        tag: String,
+        /// What to do with the Modrinth versions
+        #[arg(long, value_enum, default_value_t = RetractMode::Delete)]
+        mode: RetractMode,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Run a server that mirrors releases on GitHub webhook deliveries
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: SocketAddr,
+        /// Repository and its config file, for example 'owner/repo=owner_repo.toml'.
+        /// Can be repeated.
+        #[arg(long = "repo", value_name = "REPO=FILE", value_parser = parse_repo_config, required = true)]
+        repos: Vec<(String, PathBuf)>,
+    },
+    /// Poll GitHub for new releases and mirror them
+    Watch {
+        /// Seconds to wait between polls
+        #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+        interval: u64,
+        /// File that keeps track of the mirrored releases
+        #[arg(long, value_name = "FILE", default_value = "mirror_upload.state.json")]
+        state: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let result = run().await;
+
+    if let Err(err) = &result {
+        if actions::is_github_actions() {
+            println!("{}", actions::error_annotation(err));
+        }
+    }
+
+    result
+}
+
+async fn run() -> Result<()> {
     let client = Client::builder()
         .user_agent("Juuxel/mirror-upload")
         .build()
@@ -52,74 +171,130 @@ async fn main() -> Result<()> {
         .wrap_err("Could not find secrets")?;
     let config_path: PathBuf = args
         .config
+        .clone()
         .unwrap_or(PathBuf::from("mirror_upload.config.toml"));
-    let config: Config =
-        toml::from_str(read_file(&config_path).await?.as_str()).into_diagnostic()?;
 
-    let repo = Repo::parse(&config.github)?;
     let context = Context {
         client,
         secrets,
         progress: MultiProgress::new(),
+        rate_limits: RateLimits::default(),
     };
 
-    let github_bar = context.progress.add(ProgressBar::new_spinner());
-    github_bar.set_message("Fetching GitHub release...");
-    github_bar.set_style(simple_progress_spinner_style());
-    let release = GetReleaseByTagName {
-        owner: repo.owner,
-        repo: repo.name,
-        tag: args.version_tag,
-    }
-    .request(&context)
-    .await?;
-    github_bar.finish_with_message("Found GitHub release!");
-
-    if release.assets.is_empty() {
-        return Err(miette!("No assets in GitHub release!"));
-    }
+    match args.command {
+        Some(Command::Backfill {
+            from,
+            to,
+            since,
+            until,
+            limit,
+            platform,
+            force,
+        }) => {
+            let options = BackfillOptions {
+                from_tag: from,
+                to_tag: to,
+                since,
+                until,
+                limit,
+                platforms: if platform.is_empty() {
+                    Platform::values()
+                } else {
+                    platform
+                },
+                force,
+            };
+            let config = Config::read(&config_path).await?;
+            let source = source::from_config(&config)?;
+            let store = AssetStore::new(source.as_ref(), args.asset_cache);
+            backfill(&context, &store, &config, &options).await
+        }
+        Some(Command::Local {
+            files,
+            version,
+            name,
+            changelog_file,
+        }) => {
+            let source = LocalSource {
+                files,
+                name,
+                changelog_file,
+            };
+            let config = Config::read(&config_path).await?;
+            let store = AssetStore::new(&source, None);
+            let (_, publications) = publish_tag(&context, &store, &config, &version).await?;
 
-    let projects = if let Some(projects) = &config.projects {
-        projects.clone()
-    } else {
-        vec![Project::empty()]
-    };
+            if actions::is_github_actions() {
+                actions::write_results(&version, &publications).await?;
+            }
 
-    let project_count = projects.len();
-    let project_bar = context.progress.add(ProgressBar::new_spinner());
-    project_bar.set_style(simple_progress_spinner_style());
+            Ok(())
+        }
+        None => {
+            let version_tag = if let Some(tag) = args.version_tag {
+                tag
+            } else if actions::is_github_actions() {
+                actions::infer_tag().await?.ok_or_else(|| {
+                    miette!("Could not infer version tag from the GitHub Actions event")
+                })?
+            } else {
+                return Err(miette!("Missing version tag"));
+            };
+            let config = Config::read(&config_path).await?;
+            let publications =
+                mirror_release(&context, &config, &version_tag, args.asset_cache).await?;
 
-    for (i, project) in projects.iter().enumerate() {
-        project_bar.set_message(format!("Publishing project {}/{}", i + 1, project_count));
+            if actions::is_github_actions() {
+                actions::write_results(&version_tag, &publications).await?;
+            }
 
-        if let Some(settings) = project.get_modrinth(&config) {
-            let bar = context.progress.add(ProgressBar::new_spinner());
-            bar.set_message(format!(
-                "Uploading to {}...",
-                console::style("Modrinth").green()
-            ));
-            bar.set_style(simple_progress_spinner_style());
-            upload_to_modrinth(&context, &config, project, &release, settings).await?;
-            bar.finish_and_clear();
+            Ok(())
         }
-
-        if let Some(settings) = project.get_curseforge(&config) {
-            let bar = context.progress.add(ProgressBar::new_spinner());
-            bar.set_message(format!(
-                "Uploading to {}...",
-                console::style("CurseForge").red()
-            ));
-            bar.set_style(simple_progress_spinner_style());
-            upload_to_curseforge(&context, &config, project, &release, settings).await?;
-            bar.finish_and_clear();
+        Some(Command::Sync { tag, yes }) => {
+            let config = Config::read(&config_path).await?;
+            let source = source::from_config(&config)?;
+            let options = SyncOptions { assume_yes: yes };
+            sync(&context, source.as_ref(), &config, &tag, &options).await
+        }
+        Some(Command::SyncPage { dry_run, yes }) => {
+            let config = Config::read(&config_path).await?;
+            let options = SyncPageOptions {
+                dry_run,
+                assume_yes: yes,
+            };
+            sync_pages(&context, &config, &options).await
+        }
+        Some(Command::Gallery) => {
+            let config = Config::read(&config_path).await?;
+            upload_gallery(&context, &config).await
+        }
+        Some(Command::Retract { tag, mode, yes }) => {
+            let config = Config::read(&config_path).await?;
+            let source = source::from_config(&config)?;
+            let options = RetractOptions {
+                mode,
+                assume_yes: yes,
+            };
+            retract(&context, source.as_ref(), &config, &tag, &options).await
+        }
+        Some(Command::Serve { address, repos }) => {
+            let options = ServerOptions {
+                address,
+                repositories: repos.into_iter().collect(),
+                asset_cache: args.asset_cache,
+            };
+            serve(context, options).await
+        }
+        Some(Command::Watch { interval, state }) => {
+            let config = Config::read(&config_path).await?;
+            let options = WatchOptions {
+                interval: Duration::from_secs(interval),
+                state_file: state,
+                asset_cache: args.asset_cache,
+            };
+            watch(&context, &config, &options).await
         }
-
-        project_bar.inc(1);
     }
-
-    project_bar.finish_with_message(format!("Published {} projects", project_count));
-
-    Ok(())
 }
 
 async fn get_secrets(args: &Args) -> Result<Secrets> {
@@ -151,6 +326,9 @@ async fn get_secrets(args: &Args) -> Result<Secrets> {
                             .to_report()
                     })?,
                 curseforge_token: get_env("CURSEFORGE_TOKEN")?,
+                gitlab_token: get_env("GITLAB_TOKEN")?,
+                gitea_token: get_env("GITEA_TOKEN")?,
+                webhook_secret: get_env("WEBHOOK_SECRET")?,
             }
         } else {
             let secrets_str = read_file(&path).await?;
@@ -160,6 +338,13 @@ async fn get_secrets(args: &Args) -> Result<Secrets> {
     Ok(secrets)
 }
 
+fn parse_repo_config(value: &str) -> Result<(String, PathBuf), String> {
+    let (repo, path) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected 'owner/repo=FILE', found {}", value))?;
+    Ok((repo.to_string(), PathBuf::from(path)))
+}
+
 fn get_env(key: &str) -> Result<Option<String>> {
     let result = std::env::var(key);
     match result {
@@ -0,0 +1,72 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use miette::Result;
+
+use crate::config::ModrinthSettings;
+use crate::modrinth::{GetProject, GetProjectVersions, ModrinthProject, Version};
+use crate::requests::{ApiRequest, Context};
+
+/// The Modrinth projects and versions fetched during a run, so that each
+/// project is only requested once however many releases are published.
+#[derive(Default)]
+pub struct ModrinthCache {
+    /// Projects keyed by the configured project ID.
+    projects: HashMap<String, Arc<ModrinthProject>>,
+    /// Versions keyed by the configured project ID.
+    versions: HashMap<String, Arc<Vec<Version>>>,
+}
+
+impl ModrinthCache {
+    /// Gets the project of the settings, fetching it if needed.
+    pub async fn project(
+        &mut self,
+        context: &Context,
+        settings: &ModrinthSettings,
+    ) -> Result<Arc<ModrinthProject>> {
+        if let Some(project) = self.projects.get(&settings.project_id) {
+            return Ok(project.clone());
+        }
+
+        let project = Arc::new(
+            GetProject {
+                api_url: settings.api_url(),
+                id_or_slug: &settings.project_id,
+            }
+            .request(context)
+            .await?,
+        );
+        self.projects
+            .insert(settings.project_id.clone(), project.clone());
+        Ok(project)
+    }
+
+    /// Gets the versions of the project of the settings, fetching them if needed.
+    pub async fn versions(
+        &mut self,
+        context: &Context,
+        settings: &ModrinthSettings,
+    ) -> Result<Arc<Vec<Version>>> {
+        if let Some(versions) = self.versions.get(&settings.project_id) {
+            return Ok(versions.clone());
+        }
+
+        let versions = Arc::new(
+            GetProjectVersions {
+                api_url: settings.api_url(),
+                project_id: &settings.project_id,
+            }
+            .request(context)
+            .await?,
+        );
+        self.versions
+            .insert(settings.project_id.clone(), versions.clone());
+        Ok(versions)
+    }
+}
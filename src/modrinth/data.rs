@@ -6,7 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Copy, Clone, Default)]
+#[derive(Serialize, Deserialize, Copy, Clone, Default, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum DependencyType {
     #[default]
@@ -16,7 +16,7 @@ pub enum DependencyType {
     Embedded,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct Dependency {
     #[serde(default)]
     pub dependency_type: DependencyType,
@@ -25,10 +25,129 @@ pub struct Dependency {
     pub version_id: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum VersionType {
     Release,
     Beta,
     Alpha,
 }
+
+/// The type of an additional file of a Modrinth version.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileType {
+    RequiredResourcePack,
+    OptionalResourcePack,
+}
+
+/// A Modrinth project.
+#[derive(Deserialize, Debug)]
+pub struct ModrinthProject {
+    pub id: String,
+    pub slug: String,
+    pub project_type: String,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    pub description: String,
+    pub body: String,
+    pub categories: Vec<String>,
+    pub additional_categories: Vec<String>,
+    pub license: License,
+    pub source_url: Option<String>,
+    pub issues_url: Option<String>,
+    pub wiki_url: Option<String>,
+    pub discord_url: Option<String>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryImage>,
+}
+
+/// An image in the gallery of a Modrinth project.
+#[derive(Deserialize, Debug)]
+pub struct GalleryImage {
+    /// The URL of the re-encoded image shown on the project page.
+    pub url: String,
+    /// The URL of the image as it was uploaded.
+    pub raw_url: Option<String>,
+    pub title: Option<String>,
+}
+
+/// Loaders of projects that Modrinth lists as plugins.
+const PLUGIN_LOADERS: [&str; 9] = [
+    "bukkit",
+    "bungeecord",
+    "folia",
+    "paper",
+    "purpur",
+    "spigot",
+    "sponge",
+    "velocity",
+    "waterfall",
+];
+
+impl ModrinthProject {
+    /// Gets the type of this project as used in the URLs of the Modrinth website.
+    ///
+    /// The API reports plugins and data packs as mods,
+    /// so they are recognised from the project's loaders.
+    pub fn site_project_type(&self) -> &str {
+        if self.project_type == "mod" && !self.loaders.is_empty() {
+            if self.loaders.iter().all(|loader| loader == "datapack") {
+                return "datapack";
+            }
+
+            if self
+                .loaders
+                .iter()
+                .all(|loader| PLUGIN_LOADERS.contains(&loader.as_str()))
+            {
+                return "plugin";
+            }
+        }
+
+        &self.project_type
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct License {
+    pub id: String,
+}
+
+/// The visibility of a Modrinth version.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionStatus {
+    Listed,
+    Archived,
+    Draft,
+    Unlisted,
+}
+
+/// A version of a Modrinth project.
+#[derive(Deserialize, Debug)]
+pub struct Version {
+    pub id: String,
+    pub name: String,
+    pub version_number: String,
+    pub changelog: Option<String>,
+    pub dependencies: Vec<Dependency>,
+    pub game_versions: Vec<String>,
+    pub version_type: VersionType,
+    pub loaders: Vec<String>,
+    pub featured: bool,
+    pub files: Vec<VersionFile>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct VersionFile {
+    pub hashes: FileHashes,
+    pub filename: String,
+    pub primary: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FileHashes {
+    pub sha1: String,
+    pub sha512: String,
+}
@@ -0,0 +1,171 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use miette::{miette, Result, WrapErr};
+use semver::VersionReq;
+
+use crate::config::{ModrinthDependency, ModrinthSettings};
+use crate::error::MuError;
+use crate::modrinth::{Dependency, GetProject, GetProjectVersions, Version};
+use crate::requests::{ApiRequest, Context};
+
+/// Resolves the configured dependencies into project and version IDs.
+///
+/// Version requirements are matched against the newest version that supports
+/// one of the given game versions. Versions whose numbers aren't valid semver
+/// only match a requirement that is exactly their version number.
+pub async fn resolve_dependencies(
+    context: &Context,
+    settings: &ModrinthSettings,
+    game_versions: &[String],
+) -> Result<Vec<Dependency>> {
+    let mut result = Vec::new();
+
+    for dependency in settings.dependencies.iter().flatten() {
+        result.push(
+            resolve_dependency(context, settings.api_url(), dependency, game_versions).await?,
+        );
+    }
+
+    Ok(result)
+}
+
+async fn resolve_dependency(
+    context: &Context,
+    api_url: &str,
+    dependency: &ModrinthDependency,
+    game_versions: &[String],
+) -> Result<Dependency> {
+    let project_id = match (&dependency.project_id, &dependency.slug) {
+        (Some(id), _) => Some(id.clone()),
+        (None, Some(slug)) => Some(
+            GetProject {
+                api_url,
+                id_or_slug: slug,
+            }
+            .request(context)
+            .await
+            .wrap_err(format!("Could not resolve dependency {}", slug))?
+            .id,
+        ),
+        (None, None) => None,
+    };
+
+    let version_id = if dependency.version_id.is_some() {
+        dependency.version_id.clone()
+    } else if dependency.version.is_some() || dependency.version_number.is_some() {
+        let Some(project_id) = &project_id else {
+            return Err(miette!(
+                "Dependency versions can only be resolved with a slug or project_id"
+            ));
+        };
+        let name = dependency.slug.as_ref().unwrap_or(project_id);
+        let versions = GetProjectVersions {
+            api_url,
+            project_id,
+        }
+        .request(context)
+        .await?;
+        Some(
+            find_version(name, dependency, &versions, game_versions)?
+                .id
+                .clone(),
+        )
+    } else {
+        None
+    };
+
+    Ok(Dependency {
+        dependency_type: dependency.dependency_type,
+        file_name: dependency.file_name.clone(),
+        project_id,
+        version_id,
+    })
+}
+
+fn find_version<'a>(
+    name: &str,
+    dependency: &ModrinthDependency,
+    versions: &'a [Version],
+    game_versions: &[String],
+) -> Result<&'a Version> {
+    if let Some(version_number) = &dependency.version_number {
+        return versions
+            .iter()
+            .find(|version| &version.version_number == version_number)
+            .ok_or_else(|| {
+                miette!(
+                    "Dependency {} has no version with version number {}",
+                    name,
+                    version_number
+                )
+            });
+    }
+
+    let compatible: Vec<&Version> = versions
+        .iter()
+        .filter(|version| {
+            version
+                .game_versions
+                .iter()
+                .any(|game_version| game_versions.contains(game_version))
+        })
+        .collect();
+    let (semver_versions, invalid): (Vec<_>, Vec<_>) = compatible
+        .into_iter()
+        .map(|version| {
+            let number = version.version_number.trim_start_matches('v');
+            (semver::Version::parse(number).ok(), version)
+        })
+        .partition(|(number, _)| number.is_some());
+
+    let requirement = dependency.version.as_deref().unwrap_or("*");
+
+    // Versions that aren't valid semver can only be matched exactly.
+    if let Some((_, version)) = invalid
+        .iter()
+        .find(|(_, version)| version.version_number == requirement)
+    {
+        return Ok(version);
+    }
+
+    let requirement = VersionReq::parse(requirement).map_err(|err| {
+        MuError::new(format!(
+            "Invalid version requirement {} for dependency {}",
+            requirement, name
+        ))
+        .cause(err)
+        .to_report()
+    })?;
+
+    semver_versions
+        .into_iter()
+        .filter_map(|(number, version)| Some((number?, version)))
+        .filter(|(number, _)| requirement.matches(number))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, version)| version)
+        .ok_or_else(|| {
+            let skipped = if invalid.is_empty() {
+                String::new()
+            } else {
+                let numbers: Vec<&str> = invalid
+                    .iter()
+                    .map(|(_, version)| version.version_number.as_str())
+                    .collect();
+                format!(
+                    " (skipped versions that aren't valid semver: {})",
+                    numbers.join(", ")
+                )
+            };
+            miette!(
+                "No version of dependency {} matches {} for game versions {}{}",
+                name,
+                requirement,
+                game_versions.join(", "),
+                skipped
+            )
+        })
+}
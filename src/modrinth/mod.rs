@@ -4,8 +4,14 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+mod cache;
 mod data;
+mod dependencies;
 mod requests;
+mod tags;
 
+pub use cache::*;
 pub use data::*;
+pub use dependencies::*;
 pub use requests::*;
+pub use tags::*;
@@ -4,19 +4,33 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use miette::{miette, IntoDiagnostic, Result, WrapErr};
 use regex::Regex;
 use reqwest::header::CONTENT_TYPE;
 use serde::Serialize;
+use sha1::{Digest, Sha1};
 
-use crate::config::{Config, ModrinthSettings, Project, ReleaseLevel};
-use crate::github::{Asset, GetAsset, Release};
-use crate::modrinth::{Dependency, VersionType};
+use crate::config::{Config, ModrinthSettings, OnExisting, Project, ReleaseLevel};
+use crate::github::without_links_section;
+use crate::modrinth::{
+    resolve_dependencies, Dependency, FileType, ModrinthCache, ModrinthProject, Version,
+    VersionStatus, VersionType,
+};
+use crate::publish::{Platform, Publication};
 use crate::requests::multipart::Form;
-use crate::requests::{body_with_progress, Context};
+use crate::requests::{
+    body_with_progress, json_with_progress, send_rate_limited_with, ApiRequest, Context,
+    RateLimitedSend,
+};
+use crate::source::{Asset, Release, ReleaseSource};
 use crate::template::Template;
 
-pub const API_URL: &str = "https://api.modrinth.com/v2";
+pub const DEFAULT_API_URL: &str = "https://api.modrinth.com/v2";
 pub const AUTH_KEY: &str = "Authorization";
 
 #[derive(Serialize)]
@@ -29,73 +43,513 @@ pub struct CreateVersionData {
     pub version_type: VersionType,
     pub loaders: Vec<String>,
     pub featured: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<VersionStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_status: Option<VersionStatus>,
     pub project_id: String,
     pub file_parts: Vec<String>,
     pub primary_file: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_types: BTreeMap<String, FileType>,
 }
 
-pub async fn upload_to_modrinth(
-    context: &Context,
-    config: &Config,
-    project: &Project,
-    release: &Release,
-    settings: &ModrinthSettings,
-) -> Result<()> {
-    let mut form = Form::new();
-    let file_regex: Option<Regex> = project.get_regex(config)?;
-    let assets: Vec<&Asset> = release.get_assets(&file_regex);
-    let file_parts: Vec<String> = assets.iter().map(|asset| asset.name.clone()).collect();
+/// The data part of a request that adds files to a version.
+#[derive(Serialize)]
+struct AddFilesData {
+    file_types: BTreeMap<String, FileType>,
+}
+
+/// The editable metadata of a Modrinth version.
+/// Fields that are `None` are left unchanged.
+#[derive(Serialize, Default)]
+pub struct EditVersionData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<Vec<Dependency>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_versions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_type: Option<VersionType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loaders: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub featured: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<VersionStatus>,
+}
+
+impl From<&CreateVersionData> for EditVersionData {
+    fn from(data: &CreateVersionData) -> Self {
+        EditVersionData {
+            name: Some(data.name.clone()),
+            changelog: data.changelog.clone(),
+            dependencies: Some(data.dependencies.clone()),
+            game_versions: Some(data.game_versions.clone()),
+            version_type: Some(data.version_type),
+            loaders: Some(data.loaders.clone()),
+            featured: Some(data.featured),
+            status: None,
+        }
+    }
+}
+
+/// Edits the metadata of a Modrinth version.
+pub struct ModifyVersion<'a> {
+    pub api_url: &'a str,
+    pub version_id: &'a str,
+    pub data: &'a EditVersionData,
+}
+
+#[async_trait]
+impl ApiRequest<()> for ModifyVersion<'_> {
+    async fn request(&self, context: &Context) -> Result<()> {
+        let url = format!("{}/version/{}", self.api_url, self.version_id);
+        let response = context
+            .client
+            .patch(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .json(self.data)
+            .send_rate_limited(context)
+            .await?;
 
-    let version_number = if let Some(template) = &settings.version_number {
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not update Modrinth version {}: {}\n{}",
+                self.version_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct ScheduleVersionData<'a> {
+    time: &'a DateTime<Utc>,
+    requested_status: VersionStatus,
+}
+
+/// Schedules a Modrinth version to be published at a given time.
+pub struct ScheduleVersion<'a> {
+    pub api_url: &'a str,
+    pub version_id: &'a str,
+    pub time: &'a DateTime<Utc>,
+    pub requested_status: VersionStatus,
+}
+
+#[async_trait]
+impl ApiRequest<()> for ScheduleVersion<'_> {
+    async fn request(&self, context: &Context) -> Result<()> {
+        let url = format!("{}/version/{}/schedule", self.api_url, self.version_id);
+        let response = context
+            .client
+            .post(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .json(&ScheduleVersionData {
+                time: self.time,
+                requested_status: self.requested_status,
+            })
+            .send_rate_limited(context)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not schedule Modrinth version {}: {}\n{}",
+                self.version_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Deletes a Modrinth version.
+pub struct DeleteVersion<'a> {
+    pub api_url: &'a str,
+    pub version_id: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<()> for DeleteVersion<'_> {
+    async fn request(&self, context: &Context) -> Result<()> {
+        let url = format!("{}/version/{}", self.api_url, self.version_id);
+        let response = context
+            .client
+            .delete(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .send_rate_limited(context)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not delete Modrinth version {}: {}\n{}",
+                self.version_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Gets a Modrinth project by its ID or slug.
+pub struct GetProject<'a> {
+    pub api_url: &'a str,
+    pub id_or_slug: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<ModrinthProject> for GetProject<'_> {
+    async fn request(&self, context: &Context) -> Result<ModrinthProject> {
+        let url = format!("{}/project/{}", self.api_url, self.id_or_slug);
+        let response = context
+            .client
+            .get(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .send_rate_limited(context)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get project {} from Modrinth: {}\n{}",
+                self.id_or_slug,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+/// The editable content of a Modrinth project page.
+/// Fields that are `None` are left unchanged.
+#[derive(Serialize, Default)]
+pub struct EditProjectData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_categories: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wiki_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discord_url: Option<String>,
+}
+
+/// Edits the page of a Modrinth project.
+pub struct ModifyProject<'a> {
+    pub api_url: &'a str,
+    pub project_id: &'a str,
+    pub data: &'a EditProjectData,
+}
+
+#[async_trait]
+impl ApiRequest<()> for ModifyProject<'_> {
+    async fn request(&self, context: &Context) -> Result<()> {
+        let url = format!("{}/project/{}", self.api_url, self.project_id);
+        let response = context
+            .client
+            .patch(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .json(self.data)
+            .send_rate_limited(context)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not update Modrinth project {}: {}\n{}",
+                self.project_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds an image to the gallery of a Modrinth project.
+pub struct AddGalleryImage<'a> {
+    pub api_url: &'a str,
+    pub project_id: &'a str,
+    /// The file extension of the image.
+    pub ext: &'a str,
+    pub featured: bool,
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub image: Bytes,
+}
+
+#[async_trait]
+impl ApiRequest<()> for AddGalleryImage<'_> {
+    async fn request(&self, context: &Context) -> Result<()> {
+        let url = format!("{}/project/{}/gallery", self.api_url, self.project_id);
+        let mut query = vec![
+            ("ext", self.ext.to_string()),
+            ("featured", self.featured.to_string()),
+        ];
+        if let Some(title) = self.title {
+            query.push(("title", title.to_string()));
+        }
+        if let Some(description) = self.description {
+            query.push(("description", description.to_string()));
+        }
+
+        let response = send_rate_limited_with(context, || {
+            context
+                .client
+                .post(&url)
+                .query(&query)
+                .header(AUTH_KEY, &context.secrets.github_token)
+                .body(body_with_progress(context, self.image.clone()))
+        })
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not add gallery image to Modrinth project {}: {}\n{}",
+                self.project_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Lists all versions of a Modrinth project.
+pub struct GetProjectVersions<'a> {
+    pub api_url: &'a str,
+    pub project_id: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<Vec<Version>> for GetProjectVersions<'_> {
+    async fn request(&self, context: &Context) -> Result<Vec<Version>> {
+        let url = format!("{}/project/{}/version", self.api_url, self.project_id);
+        let response = context
+            .client
+            .get(url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .send_rate_limited(context)
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(miette!(
+                "Could not get versions of project {} from Modrinth: {}\n{}",
+                self.project_id,
+                response.status(),
+                response.text().await.into_diagnostic()?
+            ));
+        }
+
+        json_with_progress(context, response).await
+    }
+}
+
+/// Computes the Modrinth version number of a release
+/// using the `version_number` template in the settings.
+pub fn get_version_number(settings: &ModrinthSettings, release: &Release) -> Result<String> {
+    get_tag_version_number(settings, &release.tag_name)
+}
+
+/// Computes the Modrinth version number of a release tag
+/// using the `version_number` template in the settings.
+pub fn get_tag_version_number(settings: &ModrinthSettings, tag: &str) -> Result<String> {
+    if let Some(template) = &settings.version_number {
         Template::parse(template)
             .and_then(|template| {
                 template.resolve(|key| match key {
-                    "tag" => Some(&release.tag_name),
+                    "tag" => Some(tag),
                     _ => None,
                 })
             })
-            .wrap_err("Could not compute Modrinth version number")?
+            .wrap_err("Could not compute Modrinth version number")
     } else {
-        release.tag_name.clone()
-    };
+        Ok(tag.to_string())
+    }
+}
 
-    let primary_file = file_parts.first().unwrap().to_string();
+/// Computes the metadata of the Modrinth version of a release.
+pub async fn get_version_data(
+    context: &Context,
+    config: &Config,
+    project: &Project,
+    release: &Release,
+    settings: &ModrinthSettings,
+    file_parts: Vec<String>,
+) -> Result<CreateVersionData> {
+    let primary_file = file_parts.first().cloned().unwrap_or_default();
+    let file_types = get_file_types(settings, &file_parts)?;
     let name = release.name.clone().unwrap_or(release.tag_name.clone());
-    let data = CreateVersionData {
+    let game_versions = project.get_game_versions(config)?;
+    Ok(CreateVersionData {
         name,
-        version_number: version_number.clone(), // TODO: Take these by reference instead
-        changelog: release.body.clone(),
-        dependencies: settings.dependencies.clone().unwrap_or(vec![]),
-        game_versions: project.get_game_versions(config)?,
+        version_number: get_version_number(settings, release)?,
+        changelog: release.body.as_deref().map(without_links_section),
+        dependencies: resolve_dependencies(context, settings, &game_versions).await?,
+        game_versions,
         version_type: ReleaseLevel::get(config, release).as_modrinth(),
         loaders: project
             .get_loaders(config)?
             .iter()
             .map(|loader| loader.modrinth_id().to_string())
             .collect(),
-        featured: false,
+        featured: settings.featured.unwrap_or(false),
+        status: if settings.publish_at.is_some() {
+            // Keep the version hidden until the scheduled time.
+            Some(settings.status.unwrap_or(VersionStatus::Draft))
+        } else {
+            settings.status
+        },
+        requested_status: settings.requested_status,
         project_id: settings.project_id.clone(),
         file_parts,
         primary_file,
+        file_types,
+    })
+}
+
+/// Assigns file types to files using the first matching rule in the settings.
+fn get_file_types(
+    settings: &ModrinthSettings,
+    file_names: &[String],
+) -> Result<BTreeMap<String, FileType>> {
+    let mut result = BTreeMap::new();
+
+    for rule in settings.file_types.iter().flatten() {
+        let regex = Regex::new(&rule.regex).into_diagnostic()?;
+
+        for file_name in file_names {
+            if regex.is_match(file_name) && !result.contains_key(file_name) {
+                result.insert(file_name.clone(), rule.file_type);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub async fn upload_to_modrinth(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    project: &Project,
+    release: &Release,
+    settings: &ModrinthSettings,
+    cache: &mut ModrinthCache,
+) -> Result<Publication> {
+    if let Some(time) = settings.publish_at {
+        if time <= Utc::now() {
+            return Err(miette!(
+                "publish_at {} of Modrinth project {} is in the past",
+                time.to_rfc3339(),
+                settings.project_id
+            ));
+        }
+    }
+
+    let mut form = Form::new();
+    let assets: Vec<&Asset> = project.get_assets(config, release)?;
+    let file_parts: Vec<String> = assets.iter().map(|asset| asset.name.clone()).collect();
+
+    let modrinth_project = cache.project(context, settings).await?;
+    let data = get_version_data(context, config, project, release, settings, file_parts).await?;
+    let version_number = data.version_number.clone();
+    let on_existing = settings.on_existing.unwrap_or_default();
+    let versions = if on_existing == OnExisting::Create {
+        Default::default()
+    } else {
+        cache.versions(context, settings).await?
     };
+    let existing = find_existing_version(&versions, &version_number, &data.loaders)?;
+
+    if let Some(existing) = existing {
+        match on_existing {
+            // The versions aren't fetched in this case.
+            OnExisting::Create => unreachable!(),
+            OnExisting::Skip => {
+                context
+                    .progress
+                    .println(format!(
+                        "Version {} already exists on Modrinth, skipping",
+                        version_number
+                    ))
+                    .into_diagnostic()?;
+            }
+            OnExisting::Error => {
+                return Err(miette!(
+                    "Version {} already exists on Modrinth project {}",
+                    version_number,
+                    settings.project_id
+                ));
+            }
+            OnExisting::Update => {
+                ModifyVersion {
+                    api_url: settings.api_url(),
+                    version_id: &existing.id,
+                    data: &EditVersionData::from(&data),
+                }
+                .request(context)
+                .await?;
+                add_files_to_modrinth(
+                    context, source, config, project, release, settings, existing,
+                )
+                .await?;
+                context
+                    .progress
+                    .println(format!(
+                        "Updated existing Modrinth version {}",
+                        version_number
+                    ))
+                    .into_diagnostic()?;
+            }
+        }
+
+        let publication = get_publication(settings, &modrinth_project, existing);
+        print_link(context, &publication)?;
+        return Ok(publication);
+    }
+
     form.text("data", serde_json::to_string(&data).into_diagnostic()?);
 
     for asset in assets {
-        GetAsset(asset)
-            .attach_to_form(context, &mut form, asset.name.clone())
+        source
+            .attach_to_form(context, &mut form, asset, asset.name.clone())
             .await?;
     }
 
-    let url = format!("{}/version", API_URL);
-    let response = context
-        .client
-        .post(url)
-        .header(AUTH_KEY, &context.secrets.github_token)
-        .header(CONTENT_TYPE, form.content_type())
-        .body(body_with_progress(context, form.bytes()))
-        .send()
-        .await
-        .into_diagnostic()?;
+    let url = format!("{}/version", settings.api_url());
+    let body = form.bytes();
+    let response = send_rate_limited_with(context, || {
+        context
+            .client
+            .post(&url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .header(CONTENT_TYPE, form.content_type())
+            .body(body_with_progress(context, body.clone()))
+    })
+    .await?;
 
     if !response.status().is_success() {
         return Err(miette!(
@@ -105,18 +559,200 @@ pub async fn upload_to_modrinth(
         ));
     }
 
-    // Print a link to the version. We can use the project ID
-    // on Modrinth if the slug is missing.
+    let version: Version = response.json().await.into_diagnostic()?;
+
+    if let Some(time) = &settings.publish_at {
+        ScheduleVersion {
+            api_url: settings.api_url(),
+            version_id: &version.id,
+            time,
+            requested_status: settings.requested_status.unwrap_or(VersionStatus::Listed),
+        }
+        .request(context)
+        .await?;
+        context
+            .progress
+            .println(format!(
+                "Scheduled Modrinth version {} to be published at {}",
+                version.version_number,
+                time.to_rfc3339()
+            ))
+            .into_diagnostic()?;
+    }
+
+    let publication = get_publication(settings, &modrinth_project, &version);
+    print_link(context, &publication)?;
+    Ok(publication)
+}
+
+/// Uploads the matching assets of a release that are missing from
+/// an existing Modrinth version.
+///
+/// Returns the names of the added files.
+pub async fn add_files_to_modrinth(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    project: &Project,
+    release: &Release,
+    settings: &ModrinthSettings,
+    version: &Version,
+) -> Result<Vec<String>> {
+    let missing = find_missing_files(context, source, config, project, release, version).await?;
+    upload_missing_files(context, settings, version, missing).await
+}
+
+/// Downloads the matching assets of a release that are missing from
+/// an existing Modrinth version. Files are matched by their SHA-1 hashes.
+pub async fn find_missing_files<'a>(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    project: &Project,
+    release: &'a Release,
+    version: &Version,
+) -> Result<Vec<(&'a Asset, Bytes)>> {
+    let file_regex: Option<Regex> = project.get_regex(config)?;
+    let mut missing = Vec::new();
+
+    for asset in release.get_assets(&file_regex) {
+        let bytes = source.download_asset(context, asset).await?;
+        let sha1 = hex::encode(Sha1::digest(&bytes));
+
+        if !version.files.iter().any(|file| file.hashes.sha1 == sha1) {
+            missing.push((asset, bytes));
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Uploads files found by [`find_missing_files`] to an existing Modrinth version.
+///
+/// Returns the names of the added files.
+pub async fn upload_missing_files(
+    context: &Context,
+    settings: &ModrinthSettings,
+    version: &Version,
+    missing: Vec<(&Asset, Bytes)>,
+) -> Result<Vec<String>> {
+    if missing.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let added: Vec<String> = missing
+        .iter()
+        .map(|(asset, _)| asset.name.clone())
+        .collect();
+    let data = AddFilesData {
+        file_types: get_file_types(settings, &added)?,
+    };
+
+    // The data part must come before the files.
+    let mut form = Form::new();
+    form.text("data", serde_json::to_string(&data).into_diagnostic()?);
+
+    for (asset, bytes) in missing {
+        form.file(asset.name.clone(), &asset.name, bytes);
+    }
+
+    let url = format!("{}/version/{}/file", settings.api_url(), version.id);
+    let body = form.bytes();
+    let response = send_rate_limited_with(context, || {
+        context
+            .client
+            .post(&url)
+            .header(AUTH_KEY, &context.secrets.github_token)
+            .header(CONTENT_TYPE, form.content_type())
+            .body(body_with_progress(context, body.clone()))
+    })
+    .await?;
+
+    if !response.status().is_success() {
+        return Err(miette!(
+            "Could not add files to Modrinth version {}: {}\n{}",
+            version.version_number,
+            response.status(),
+            response.text().await.into_diagnostic()?
+        ));
+    }
+
     context
         .progress
         .println(format!(
-            "{} https://modrinth.com/mod/{}/version/{}",
-            console::style("Link:").bold().blue(),
-            settings.slug.as_ref().unwrap_or(&settings.project_id),
-            version_number
+            "Added {} to Modrinth version {}",
+            added.join(", "),
+            version.version_number
         ))
-        .into_diagnostic()
-        .wrap_err("Could not print link to release")?;
+        .into_diagnostic()?;
+
+    Ok(added)
+}
+
+/// Prints a link to the version.
+fn print_link(context: &Context, publication: &Publication) -> Result<()> {
+    if let Some(url) = &publication.url {
+        context
+            .progress
+            .println(format!("{} {}", console::style("Link:").bold().blue(), url))
+            .into_diagnostic()
+            .wrap_err("Could not print link to release")?;
+    }
 
     Ok(())
 }
+
+/// Finds the version with a version number that shares a loader with `loaders`.
+///
+/// Projects for different loaders can publish the same version number
+/// to one Modrinth project, so matching on the number alone could pick
+/// the version of another loader.
+pub fn find_existing_version<'a>(
+    versions: &'a [Version],
+    version_number: &str,
+    loaders: &[String],
+) -> Result<Option<&'a Version>> {
+    let matching: Vec<&Version> = versions
+        .iter()
+        .filter(|version| {
+            version.version_number == version_number
+                && version
+                    .loaders
+                    .iter()
+                    .any(|loader| loaders.contains(loader))
+        })
+        .collect();
+
+    if matching.len() > 1 {
+        let ids: Vec<&str> = matching.iter().map(|version| version.id.as_str()).collect();
+        return Err(miette!(
+            "Several Modrinth versions have version number {} and loaders {}: {}",
+            version_number,
+            loaders.join(", "),
+            ids.join(", ")
+        ));
+    }
+
+    Ok(matching.first().copied())
+}
+
+/// Describes a version of a Modrinth project as a [`Publication`].
+pub fn get_publication(
+    settings: &ModrinthSettings,
+    project: &ModrinthProject,
+    version: &Version,
+) -> Publication {
+    let slug = settings.slug.as_ref().unwrap_or(&project.slug);
+    Publication {
+        platform: Platform::Modrinth,
+        project: slug.clone(),
+        id: version.id.clone(),
+        url: Some(format!(
+            "{}/{}/{}/version/{}",
+            settings.site_url(),
+            project.site_project_type(),
+            slug,
+            version.id
+        )),
+    }
+}
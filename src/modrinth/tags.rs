@@ -0,0 +1,191 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use miette::{miette, IntoDiagnostic, Result};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::config::{Config, ModrinthSettings, Project};
+use crate::error::MuError;
+use crate::modrinth::{ModrinthProject, AUTH_KEY};
+use crate::requests::{json_with_progress, ApiRequest, Context, RateLimitedSend};
+
+/// How long the fetched tags are reused before fetching them again.
+const TAG_CACHE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// The largest edit distance for which a value is suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+#[derive(Deserialize)]
+pub struct GameVersionTag {
+    pub version: String,
+}
+
+#[derive(Deserialize)]
+pub struct LoaderTag {
+    pub name: String,
+    pub supported_project_types: Vec<String>,
+}
+
+/// Lists the game versions known to Modrinth.
+pub struct GetGameVersionTags<'a> {
+    pub api_url: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<Vec<GameVersionTag>> for GetGameVersionTags<'_> {
+    async fn request(&self, context: &Context) -> Result<Vec<GameVersionTag>> {
+        get_tag(context, self.api_url, "game_version").await
+    }
+}
+
+/// Lists the loaders known to Modrinth.
+pub struct GetLoaderTags<'a> {
+    pub api_url: &'a str,
+}
+
+#[async_trait]
+impl ApiRequest<Vec<LoaderTag>> for GetLoaderTags<'_> {
+    async fn request(&self, context: &Context) -> Result<Vec<LoaderTag>> {
+        get_tag(context, self.api_url, "loader").await
+    }
+}
+
+async fn get_tag<T: serde::de::DeserializeOwned>(
+    context: &Context,
+    api_url: &str,
+    tag: &str,
+) -> Result<T> {
+    let url = format!("{}/tag/{}", api_url, tag);
+    let response = context
+        .client
+        .get(url)
+        .header(AUTH_KEY, &context.secrets.github_token)
+        .send_rate_limited(context)
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(miette!(
+            "Could not get {} tags from Modrinth: {}\n{}",
+            tag,
+            response.status(),
+            response.text().await.into_diagnostic()?
+        ));
+    }
+
+    json_with_progress(context, response).await
+}
+
+struct Tags {
+    game_versions: Vec<String>,
+    loaders: Vec<LoaderTag>,
+    fetched_at: Instant,
+}
+
+/// The fetched tags, keyed by API URL.
+static TAGS: Mutex<BTreeMap<String, Arc<Tags>>> = Mutex::const_new(BTreeMap::new());
+
+async fn get_tags(context: &Context, api_url: &str) -> Result<Arc<Tags>> {
+    let mut tags = TAGS.lock().await;
+
+    if let Some(tags) = tags.get(api_url) {
+        if tags.fetched_at.elapsed() < TAG_CACHE_DURATION {
+            return Ok(tags.clone());
+        }
+    }
+
+    let fetched = Arc::new(Tags {
+        game_versions: GetGameVersionTags { api_url }
+            .request(context)
+            .await?
+            .into_iter()
+            .map(|tag| tag.version)
+            .collect(),
+        loaders: GetLoaderTags { api_url }.request(context).await?,
+        fetched_at: Instant::now(),
+    });
+    tags.insert(api_url.to_string(), fetched.clone());
+    Ok(fetched)
+}
+
+/// Checks that Modrinth knows the game versions and loaders of a project.
+pub async fn validate_modrinth_project(
+    context: &Context,
+    config: &Config,
+    project: &Project,
+) -> Result<()> {
+    let Some(settings) = project.get_modrinth(config) else {
+        return Ok(());
+    };
+    let tags = get_tags(context, settings.api_url()).await?;
+
+    for game_version in project.get_game_versions(config)? {
+        check_known("game version", &game_version, &tags.game_versions)?;
+    }
+
+    let loaders: Vec<String> = tags.loaders.iter().map(|tag| tag.name.clone()).collect();
+    for loader in project.get_loaders(config)? {
+        check_known("loader", loader.modrinth_id(), &loaders)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the loaders can be used with the type of a Modrinth project.
+pub async fn validate_project_loaders(
+    context: &Context,
+    settings: &ModrinthSettings,
+    project: &ModrinthProject,
+    loaders: &[String],
+) -> Result<()> {
+    let tags = get_tags(context, settings.api_url()).await?;
+    let project_types = [project.project_type.as_str(), project.site_project_type()];
+
+    for loader in loaders {
+        let supported = tags
+            .loaders
+            .iter()
+            .find(|tag| &tag.name == loader)
+            .is_some_and(|tag| {
+                tag.supported_project_types
+                    .iter()
+                    .any(|project_type| project_types.contains(&project_type.as_str()))
+            });
+
+        if !supported {
+            return Err(miette!(
+                "Loader {} cannot be used for Modrinth project {}, which is a {}",
+                loader,
+                project.slug,
+                project.site_project_type()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_known(kind: &str, value: &str, known: &[String]) -> Result<()> {
+    if known.iter().any(|known| known == value) {
+        return Ok(());
+    }
+
+    let suggestion = known
+        .iter()
+        .map(|known| (strsim::levenshtein(value, known), known))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| format!("Did you mean {}?", known));
+
+    Err(MuError::new(format!("Unknown Modrinth {} {}", kind, value))
+        .help(suggestion)
+        .to_report())
+}
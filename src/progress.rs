@@ -4,7 +4,11 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+use std::io::Write;
+
 use indicatif::{ProgressBar, ProgressStyle};
+use miette::{IntoDiagnostic, Result};
+use tokio::io::{AsyncBufReadExt, BufReader};
 
 pub const SPINNER_CHARACTERS: &str = "-\\|/x";
 
@@ -36,3 +40,16 @@ fn network_progress_spinner_style() -> ProgressStyle {
         .unwrap()
         .tick_chars(SPINNER_CHARACTERS)
 }
+
+/// Asks the user to confirm an action on the standard streams.
+pub async fn confirm() -> Result<bool> {
+    print!("Continue? [y/N] ");
+    std::io::stdout().flush().into_diagnostic()?;
+
+    let mut answer = String::new();
+    BufReader::new(tokio::io::stdin())
+        .read_line(&mut answer)
+        .await
+        .into_diagnostic()?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
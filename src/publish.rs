@@ -0,0 +1,216 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use indicatif::ProgressBar;
+use miette::{miette, Result};
+
+use crate::config::{Config, Project};
+use crate::curseforge::upload_to_curseforge;
+use crate::github::write_release_links;
+use crate::modrinth::{
+    upload_to_modrinth, validate_modrinth_project, validate_project_loaders, ModrinthCache,
+};
+use crate::progress::simple_progress_spinner_style;
+use crate::requests::Context;
+use crate::source::{self, AssetStore, Release, ReleaseSource};
+
+/// A platform that releases can be mirrored to.
+#[derive(ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[value(rename_all = "lowercase")]
+pub enum Platform {
+    Modrinth,
+    CurseForge,
+}
+
+impl Platform {
+    pub fn values() -> Vec<Self> {
+        vec![Self::Modrinth, Self::CurseForge]
+    }
+
+    /// The display name of this platform.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Modrinth => "Modrinth",
+            Self::CurseForge => "CurseForge",
+        }
+    }
+}
+
+/// A version or file that has been published on a platform.
+#[derive(Clone, Debug)]
+pub struct Publication {
+    pub platform: Platform,
+    /// The slug of the project on the platform, or its ID if the slug is unknown.
+    pub project: String,
+    /// The Modrinth version ID or the CurseForge file ID.
+    pub id: String,
+    /// A link to the published version, if known.
+    pub url: Option<String>,
+}
+
+/// Mirrors the release with the tag from the source defined in the config
+/// to all platforms, and writes links into the release if enabled.
+pub async fn mirror_release(
+    context: &Context,
+    config: &Config,
+    tag: &str,
+    asset_cache: Option<PathBuf>,
+) -> Result<Vec<Publication>> {
+    let links_repo = config.get_release_links_repo()?;
+    let source = source::from_config(config)?;
+    let store = AssetStore::new(source.as_ref(), asset_cache);
+    let (release, publications) = publish_tag(context, &store, config, tag).await?;
+
+    if let Some(repo) = links_repo {
+        write_release_links(context, &repo, &release, &publications).await?;
+    }
+
+    Ok(publications)
+}
+
+/// Looks up a release by its tag and publishes it to all platforms.
+pub async fn publish_tag(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    tag: &str,
+) -> Result<(Release, Vec<Publication>)> {
+    let bar = context.progress.add(ProgressBar::new_spinner());
+    bar.set_message(format!("Fetching release from {}...", source.name()));
+    bar.set_style(simple_progress_spinner_style());
+    let release = source.get_release(context, tag).await?;
+    bar.finish_with_message("Found release!");
+
+    let publications =
+        publish_release(context, source, config, &release, &Platform::values()).await?;
+    Ok((release, publications))
+}
+
+/// Publishes a release to the selected platforms for every configured project.
+pub async fn publish_release(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    release: &Release,
+    platforms: &[Platform],
+) -> Result<Vec<Publication>> {
+    check_assets(release)?;
+    let mut cache = ModrinthCache::default();
+    validate_projects(context, config, platforms, &mut cache).await?;
+
+    let projects = config.get_projects();
+    let project_count = projects.len();
+    let project_bar = context.progress.add(ProgressBar::new_spinner());
+    project_bar.set_style(simple_progress_spinner_style());
+
+    let mut publications = Vec::new();
+
+    for (i, project) in projects.iter().enumerate() {
+        project_bar.set_message(format!("Publishing project {}/{}", i + 1, project_count));
+        publications.extend(
+            publish_project(
+                context, source, config, project, release, platforms, &mut cache,
+            )
+            .await?,
+        );
+        project_bar.inc(1);
+    }
+
+    project_bar.finish_with_message(format!("Published {} projects", project_count));
+    Ok(publications)
+}
+
+/// Checks that a release has assets that can be published.
+pub fn check_assets(release: &Release) -> Result<()> {
+    if release.assets.is_empty() {
+        return Err(miette!("No assets in release {}!", release.tag_name));
+    }
+
+    Ok(())
+}
+
+/// Checks the settings of every project against the selected platforms
+/// so that mistakes are caught before any assets are downloaded.
+///
+/// The fetched Modrinth projects are kept in the cache for the uploads.
+pub async fn validate_projects(
+    context: &Context,
+    config: &Config,
+    platforms: &[Platform],
+    cache: &mut ModrinthCache,
+) -> Result<()> {
+    for project in config.get_projects() {
+        if platforms.contains(&Platform::Modrinth) {
+            if let Some(settings) = project.get_modrinth(config) {
+                validate_modrinth_project(context, config, &project).await?;
+                let loaders: Vec<String> = project
+                    .get_loaders(config)?
+                    .iter()
+                    .map(|loader| loader.modrinth_id().to_string())
+                    .collect();
+                let modrinth_project = cache.project(context, settings).await?;
+                validate_project_loaders(context, settings, &modrinth_project, &loaders).await?;
+            }
+        }
+
+        if platforms.contains(&Platform::CurseForge) {
+            if let Some(settings) = project.get_curseforge(config) {
+                settings.validate_loaders(&project.get_loaders(config)?)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Publishes a release of a single project to the selected platforms.
+pub async fn publish_project(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    project: &Project,
+    release: &Release,
+    platforms: &[Platform],
+    cache: &mut ModrinthCache,
+) -> Result<Vec<Publication>> {
+    let mut publications = Vec::new();
+
+    if platforms.contains(&Platform::Modrinth) {
+        if let Some(settings) = project.get_modrinth(config) {
+            let bar = context.progress.add(ProgressBar::new_spinner());
+            bar.set_message(format!(
+                "Uploading to {}...",
+                console::style("Modrinth").green()
+            ));
+            bar.set_style(simple_progress_spinner_style());
+            publications.push(
+                upload_to_modrinth(context, source, config, project, release, settings, cache)
+                    .await?,
+            );
+            bar.finish_and_clear();
+        }
+    }
+
+    if platforms.contains(&Platform::CurseForge) {
+        if let Some(settings) = project.get_curseforge(config) {
+            let bar = context.progress.add(ProgressBar::new_spinner());
+            bar.set_message(format!(
+                "Uploading to {}...",
+                console::style("CurseForge").red()
+            ));
+            bar.set_style(simple_progress_spinner_style());
+            publications.push(
+                upload_to_curseforge(context, source, config, project, release, settings).await?,
+            );
+            bar.finish_and_clear();
+        }
+    }
+
+    Ok(publications)
+}
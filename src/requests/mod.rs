@@ -5,6 +5,7 @@
  */
 
 pub mod multipart;
+mod rate_limit;
 
 use async_trait::async_trait;
 use bytes::{BufMut, Bytes, BytesMut};
@@ -19,11 +20,13 @@ use std::convert::Infallible;
 pub use crate::config::Secrets;
 use crate::error::MuError;
 use crate::progress::network_progress_bar;
+pub use rate_limit::*;
 
 pub struct Context {
     pub client: Client,
     pub secrets: Secrets,
     pub progress: MultiProgress,
+    pub rate_limits: RateLimits,
 }
 
 #[async_trait]
@@ -95,3 +98,18 @@ pub fn body_with_progress(context: &Context, bytes: Bytes) -> Body {
     };
     Body::wrap_stream(stream)
 }
+
+/// Percent-encodes a string so that it can be used as a single URL path segment.
+pub fn encode_path_segment(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+
+    for byte in str.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            result.push(byte as char);
+        } else {
+            result += format!("%{:02X}", byte).as_str();
+        }
+    }
+
+    result
+}
@@ -0,0 +1,195 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use miette::{IntoDiagnostic, Result};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use crate::requests::Context;
+
+const REMAINING_KEY: &str = "X-Ratelimit-Remaining";
+const RESET_KEY: &str = "X-Ratelimit-Reset";
+
+/// How many times a rate-limited request is retried.
+const MAX_RETRIES: u32 = 3;
+
+/// The wait before retrying a rate-limited request
+/// when the response doesn't say how long to wait.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Below this many remaining requests, requests are spread out evenly
+/// over the time until the reset instead of using up the limit at once.
+const LOW_REMAINING: u64 = 10;
+
+/// Reset values above this are Unix timestamps instead of seconds from now.
+const MAX_RESET_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+/// Tracks the rate limits of the hosts that requests are sent to.
+#[derive(Default)]
+pub struct RateLimits {
+    hosts: Mutex<HashMap<String, HostLimit>>,
+}
+
+struct HostLimit {
+    remaining: u64,
+    reset: Instant,
+}
+
+impl RateLimits {
+    /// Gets the time to wait before sending a request to a host.
+    ///
+    /// When the rate limit has been used up, this is the time until it resets.
+    /// When only a few requests remain, the time until the reset is split
+    /// between them, so that the limit isn't hit.
+    fn wait_time(&self, host: &str) -> Option<Duration> {
+        let hosts = self.hosts.lock().unwrap();
+        let limit = hosts.get(host)?;
+        let until_reset = limit.reset.saturating_duration_since(Instant::now());
+
+        if until_reset.is_zero() || limit.remaining > LOW_REMAINING {
+            return None;
+        }
+
+        if limit.remaining == 0 {
+            return Some(until_reset);
+        }
+
+        Some(until_reset / (limit.remaining as u32 + 1))
+    }
+
+    fn update(&self, host: &str, headers: &HeaderMap) {
+        let Some(remaining) = header_number(headers, REMAINING_KEY) else {
+            return;
+        };
+        let reset = header_number(headers, RESET_KEY).map_or(Duration::ZERO, reset_duration);
+
+        self.hosts.lock().unwrap().insert(
+            host.to_string(),
+            HostLimit {
+                remaining,
+                reset: Instant::now() + reset,
+            },
+        );
+    }
+}
+
+/// Checks whether a response was rejected because of a rate limit.
+/// GitHub rejects requests over its rate limits with `403 Forbidden`
+/// instead of `429 Too Many Requests`.
+pub fn is_rate_limited(response: &Response) -> bool {
+    let headers = response.headers();
+    match response.status() {
+        StatusCode::TOO_MANY_REQUESTS => true,
+        StatusCode::FORBIDDEN => {
+            header_number(headers, REMAINING_KEY) == Some(0) || headers.contains_key(RETRY_AFTER)
+        }
+        _ => false,
+    }
+}
+
+fn header_number(headers: &HeaderMap, key: &str) -> Option<u64> {
+    headers.get(key)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn reset_duration(reset: u64) -> Duration {
+    if reset > MAX_RESET_SECONDS {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Duration::from_secs(reset.saturating_sub(now))
+    } else {
+        Duration::from_secs(reset)
+    }
+}
+
+/// Sends requests while honouring the rate limits of their hosts.
+#[async_trait]
+pub trait RateLimitedSend {
+    /// Sends this request, waiting first if the host's rate limit is nearly used up.
+    /// Requests rejected with `429 Too Many Requests` are retried
+    /// if their body can be sent again.
+    async fn send_rate_limited(self, context: &Context) -> Result<Response>;
+}
+
+#[async_trait]
+impl RateLimitedSend for RequestBuilder {
+    async fn send_rate_limited(self, context: &Context) -> Result<Response> {
+        let mut next = Some(self);
+        send_with_retries(context, move || {
+            let builder = next.take()?;
+            next = builder.try_clone();
+            Some(builder)
+        })
+        .await
+    }
+}
+
+/// Sends a request like [`RateLimitedSend::send_rate_limited`], creating it again
+/// with `make_request` for every retry. This allows retrying requests with
+/// bodies that can only be sent once, such as uploads with progress bars.
+pub async fn send_rate_limited_with<F>(context: &Context, make_request: F) -> Result<Response>
+where
+    F: Fn() -> RequestBuilder + Send + Sync,
+{
+    send_with_retries(context, || Some(make_request())).await
+}
+
+/// Sends the requests returned by `next` until one isn't rate limited,
+/// `next` returns `None` or the retries are used up.
+async fn send_with_retries<F>(context: &Context, mut next: F) -> Result<Response>
+where
+    F: FnMut() -> Option<RequestBuilder> + Send,
+{
+    let mut builder = next().expect("the first request is always created");
+    let mut retries = 0;
+
+    loop {
+        let request = builder.build().into_diagnostic()?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+
+        if let Some(wait_time) = context.rate_limits.wait_time(&host) {
+            wait(context, &host, wait_time).await?;
+        }
+
+        let response = context.client.execute(request).await.into_diagnostic()?;
+        context.rate_limits.update(&host, response.headers());
+
+        if response.status() != StatusCode::TOO_MANY_REQUESTS || retries >= MAX_RETRIES {
+            return Ok(response);
+        }
+
+        let Some(retry) = next() else {
+            return Ok(response);
+        };
+
+        let wait_time = header_number(response.headers(), RETRY_AFTER.as_str())
+            .map(Duration::from_secs)
+            .or_else(|| context.rate_limits.wait_time(&host))
+            .unwrap_or(DEFAULT_RETRY_DELAY);
+        wait(context, &host, wait_time).await?;
+        builder = retry;
+        retries += 1;
+    }
+}
+
+async fn wait(context: &Context, host: &str, wait_time: Duration) -> Result<()> {
+    context
+        .progress
+        .println(format!(
+            "Rate limit of {} nearly reached, waiting {} seconds",
+            host,
+            wait_time.as_secs_f64().ceil()
+        ))
+        .into_diagnostic()?;
+    tokio::time::sleep(wait_time).await;
+    Ok(())
+}
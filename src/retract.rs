@@ -0,0 +1,198 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use clap::ValueEnum;
+use miette::{miette, Result};
+
+use crate::config::{Config, CurseForgeSettings};
+use crate::github::get_release_links;
+use crate::modrinth::{
+    get_tag_version_number, DeleteVersion, EditVersionData, GetProjectVersions, ModifyVersion,
+    Version, VersionStatus,
+};
+use crate::progress::confirm;
+use crate::requests::{ApiRequest, Context};
+use crate::source::ReleaseSource;
+
+/// How the Modrinth versions of a retracted release are removed.
+#[derive(ValueEnum, Copy, Clone, PartialEq, Eq, Debug)]
+#[value(rename_all = "lowercase")]
+pub enum RetractMode {
+    /// Delete the versions.
+    Delete,
+    /// Mark the versions as archived.
+    Archive,
+    /// Hide the versions from the version list.
+    Unlist,
+}
+
+impl RetractMode {
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Delete => "deleted",
+            Self::Archive => "archived",
+            Self::Unlist => "unlisted",
+        }
+    }
+}
+
+pub struct RetractOptions {
+    pub mode: RetractMode,
+    /// Skip the confirmation prompt.
+    pub assume_yes: bool,
+}
+
+/// Removes the versions that were published for a release tag
+/// from every configured project.
+///
+/// CurseForge files can't be listed or deleted through the upload API,
+/// so they are not retracted. The files recorded in the release links
+/// are listed so that they can be deleted by hand.
+pub async fn retract(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    tag: &str,
+    options: &RetractOptions,
+) -> Result<()> {
+    // The project ID and API URL of each version.
+    let mut versions: Vec<(String, String, Version)> = Vec::new();
+    let mut curseforge_projects: Vec<CurseForgeSettings> = Vec::new();
+
+    for project in config.get_projects() {
+        if let Some(settings) = project.get_modrinth(config) {
+            let version_number = get_tag_version_number(settings, tag)?;
+            let matching = GetProjectVersions {
+                api_url: settings.api_url(),
+                project_id: &settings.project_id,
+            }
+            .request(context)
+            .await?
+            .into_iter()
+            .filter(|version| version.version_number == version_number);
+            versions.extend(matching.map(|version| {
+                (
+                    settings.project_id.clone(),
+                    settings.api_url().to_string(),
+                    version,
+                )
+            }));
+        }
+
+        if let Some(settings) = project.get_curseforge(config) {
+            curseforge_projects.push(settings.clone());
+        }
+    }
+
+    // Write to the standard streams since this output is
+    // part of the interactive confirmation.
+    if versions.is_empty() {
+        println!("No Modrinth versions found for {}", tag);
+    } else {
+        println!(
+            "The following Modrinth versions will be {}:",
+            options.mode.verb()
+        );
+        for (project_id, _, version) in &versions {
+            println!(
+                "- {} {} (project {}, ID {})",
+                version.name, version.version_number, project_id, version.id
+            );
+        }
+    }
+
+    if !curseforge_projects.is_empty() {
+        let links = match source.get_release(context, tag).await {
+            Ok(release) => get_release_links(release.body.as_deref().unwrap_or_default()),
+            Err(_) => vec![],
+        };
+
+        for settings in &curseforge_projects {
+            print_curseforge_files(settings, &links);
+        }
+    }
+
+    if versions.is_empty() {
+        return Ok(());
+    }
+
+    if !options.assume_yes && !confirm().await? {
+        return Err(miette!("Retraction cancelled"));
+    }
+
+    for (project_id, api_url, version) in &versions {
+        match options.mode {
+            RetractMode::Delete => {
+                DeleteVersion {
+                    api_url,
+                    version_id: &version.id,
+                }
+                .request(context)
+                .await?
+            }
+            RetractMode::Archive | RetractMode::Unlist => {
+                let status = if options.mode == RetractMode::Archive {
+                    VersionStatus::Archived
+                } else {
+                    VersionStatus::Unlisted
+                };
+                ModifyVersion {
+                    api_url,
+                    version_id: &version.id,
+                    data: &EditVersionData {
+                        status: Some(status),
+                        ..Default::default()
+                    },
+                }
+                .request(context)
+                .await?
+            }
+        }
+
+        println!(
+            "Version {} of Modrinth project {} was {}",
+            version.version_number,
+            project_id,
+            options.mode.verb()
+        );
+    }
+
+    Ok(())
+}
+
+/// Lists the CurseForge files of a project that have to be deleted by hand.
+fn print_curseforge_files(settings: &CurseForgeSettings, links: &[String]) {
+    let warning = console::style("Warning:").bold().yellow();
+    let Some(url) = settings.get_url() else {
+        println!(
+            "{} files of CurseForge project {} must be removed manually",
+            warning, settings.project_id
+        );
+        return;
+    };
+
+    let files_url = format!("{}/files/", url);
+    let files: Vec<&String> = links
+        .iter()
+        .filter(|link| link.starts_with(&files_url))
+        .collect();
+
+    if files.is_empty() {
+        println!(
+            "{} files of CurseForge project {} must be removed manually, see {}",
+            warning, settings.project_id, files_url
+        );
+        return;
+    }
+
+    println!(
+        "{} the following files of CurseForge project {} must be removed manually:",
+        warning, settings.project_id
+    );
+    for file in files {
+        println!("- {}", file);
+    }
+}
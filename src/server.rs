@@ -0,0 +1,283 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use hmac::{Hmac, Mac};
+use hyper::body::HttpBody;
+use hyper::header::CONTENT_LENGTH;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use miette::{miette, IntoDiagnostic, Result};
+use serde::Deserialize;
+use sha2::Sha256;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use crate::config::Config;
+use crate::publish::mirror_release;
+use crate::requests::Context;
+
+const EVENT_KEY: &str = "X-GitHub-Event";
+const SIGNATURE_KEY: &str = "X-Hub-Signature-256";
+const SIGNATURE_PREFIX: &str = "sha256=";
+/// GitHub caps webhook payloads at 25 MB.
+const MAX_BODY_SIZE: usize = 25 * 1024 * 1024;
+
+pub struct ServerOptions {
+    /// The address to listen on.
+    pub address: SocketAddr,
+    /// Config files of the mirrored repositories, keyed by `owner/repo`.
+    pub repositories: HashMap<String, PathBuf>,
+    /// Directory for caching downloaded assets across runs.
+    pub asset_cache: Option<PathBuf>,
+}
+
+struct ServerState {
+    secret: String,
+    /// Queues of release tags to mirror, keyed by lowercase `owner/repo`.
+    queues: HashMap<String, UnboundedSender<String>>,
+}
+
+#[derive(Deserialize)]
+struct ReleaseEvent {
+    action: String,
+    release: EventRelease,
+    repository: EventRepository,
+}
+
+#[derive(Deserialize)]
+struct EventRelease {
+    tag_name: String,
+}
+
+#[derive(Deserialize)]
+struct EventRepository {
+    full_name: String,
+}
+
+/// Runs an HTTP server that mirrors releases when GitHub delivers
+/// a `release.published` webhook event. Releases of the same repository
+/// are mirrored one at a time.
+pub async fn serve(context: Context, options: ServerOptions) -> Result<()> {
+    let secret = context
+        .secrets
+        .webhook_secret
+        .clone()
+        .ok_or_else(|| miette!("Missing webhook secret"))?;
+    let context = Arc::new(context);
+    let mut queues = HashMap::new();
+
+    for (repo, config_path) in options.repositories {
+        let (sender, mut receiver) = unbounded_channel::<String>();
+        let context = context.clone();
+        let asset_cache = options.asset_cache.clone();
+        let repo_name = repo.clone();
+
+        tokio::spawn(async move {
+            while let Some(tag) = receiver.recv().await {
+                let result = async {
+                    let config = Config::read(&config_path).await?;
+                    config.reject_publish_at("serve")?;
+                    let config_repo = config.get_github_repo()?;
+                    let config_name = format!("{}/{}", config_repo.owner, config_repo.name);
+
+                    if !config_name.eq_ignore_ascii_case(&repo_name) {
+                        return Err(miette!(
+                            "Config {} is for {}, not {}",
+                            config_path.display(),
+                            config_name,
+                            repo_name
+                        ));
+                    }
+
+                    mirror_release(&context, &config, &tag, asset_cache.clone()).await
+                }
+                .await;
+
+                // Log to the standard streams since the progress output
+                // is hidden when not running in a terminal.
+                match result {
+                    Ok(_) => println!("Mirrored {}@{}", repo_name, tag),
+                    Err(err) => eprintln!(
+                        "{} Could not mirror {}@{}: {:?}",
+                        console::style("Error:").bold().red(),
+                        repo_name,
+                        tag,
+                        err
+                    ),
+                }
+            }
+        });
+
+        queues.insert(repo.to_lowercase(), sender);
+    }
+
+    let state = Arc::new(ServerState { secret, queues });
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                handle_request(state.clone(), request)
+            }))
+        }
+    });
+
+    println!("Listening on {}", options.address);
+    Server::try_bind(&options.address)
+        .into_diagnostic()?
+        .serve(make_service)
+        .await
+        .into_diagnostic()
+}
+
+async fn handle_request(
+    state: Arc<ServerState>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::POST {
+        return Ok(respond(StatusCode::METHOD_NOT_ALLOWED, "Expected POST"));
+    }
+
+    let event = header(&request, EVENT_KEY);
+    let signature = header(&request, SIGNATURE_KEY);
+    let content_length =
+        header(&request, CONTENT_LENGTH.as_str()).and_then(|length| length.parse::<usize>().ok());
+
+    if content_length.is_some_and(|length| length > MAX_BODY_SIZE) {
+        return Ok(respond(StatusCode::PAYLOAD_TOO_LARGE, "Body too large"));
+    }
+
+    let body = match read_body(request.into_body()).await {
+        Ok(Some(body)) => body,
+        Ok(None) => return Ok(respond(StatusCode::PAYLOAD_TOO_LARGE, "Body too large")),
+        Err(_) => return Ok(respond(StatusCode::BAD_REQUEST, "Could not read body")),
+    };
+
+    if !verify_signature(&state.secret, &body, signature.as_deref()) {
+        return Ok(respond(StatusCode::UNAUTHORIZED, "Invalid signature"));
+    }
+
+    match event.as_deref() {
+        Some("ping") => return Ok(respond(StatusCode::OK, "Pong")),
+        Some("release") => {}
+        _ => return Ok(respond(StatusCode::ACCEPTED, "Ignored event")),
+    }
+
+    let event: ReleaseEvent = match serde_json::from_slice(&body) {
+        Ok(event) => event,
+        Err(_) => return Ok(respond(StatusCode::BAD_REQUEST, "Invalid release event")),
+    };
+
+    if event.action != "published" {
+        return Ok(respond(StatusCode::ACCEPTED, "Ignored release action"));
+    }
+
+    let Some(queue) = state.queues.get(&event.repository.full_name.to_lowercase()) else {
+        return Ok(respond(StatusCode::NOT_FOUND, "Unknown repository"));
+    };
+
+    if queue.send(event.release.tag_name).is_err() {
+        return Ok(respond(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Release queue is closed",
+        ));
+    }
+
+    Ok(respond(StatusCode::ACCEPTED, "Queued"))
+}
+
+/// Reads a request body, or returns `None` if it is larger than [`MAX_BODY_SIZE`].
+async fn read_body(mut body: Body) -> hyper::Result<Option<Bytes>> {
+    let mut bytes = BytesMut::new();
+
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+
+        if bytes.len() + chunk.len() > MAX_BODY_SIZE {
+            return Ok(None);
+        }
+
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Some(bytes.freeze()))
+}
+
+fn header(request: &Request<Body>, key: &str) -> Option<String> {
+    request
+        .headers()
+        .get(key)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// Checks the HMAC-SHA256 signature of a webhook delivery.
+fn verify_signature(secret: &str, body: &[u8], signature: Option<&str>) -> bool {
+    let Some(signature) = signature.and_then(|s| s.strip_prefix(SIGNATURE_PREFIX)) else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn respond(status: StatusCode, message: &'static str) -> Response<Body> {
+    let mut response = Response::new(Body::from(message));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example delivery from the GitHub webhook documentation.
+    const SECRET: &str = "It's a Secret to Everybody";
+    const BODY: &[u8] = b"Hello, World!";
+    const SIGNATURE: &str =
+        "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    #[test]
+    fn accepts_valid_signature() {
+        assert!(verify_signature(SECRET, BODY, Some(SIGNATURE)));
+    }
+
+    #[test]
+    fn rejects_wrong_signature() {
+        assert!(!verify_signature("Another secret", BODY, Some(SIGNATURE)));
+        assert!(!verify_signature(SECRET, b"Hello, World?", Some(SIGNATURE)));
+        assert!(!verify_signature(SECRET, BODY, None));
+    }
+
+    #[test]
+    fn rejects_missing_prefix() {
+        let hex = SIGNATURE.strip_prefix(SIGNATURE_PREFIX).unwrap();
+        assert!(!verify_signature(SECRET, BODY, Some(hex)));
+        assert!(!verify_signature(
+            SECRET,
+            BODY,
+            Some(&format!("sha1={}", hex))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_hex() {
+        assert!(!verify_signature(SECRET, BODY, Some("sha256=not hex")));
+        assert!(!verify_signature(SECRET, BODY, Some("sha256=757")));
+        assert!(!verify_signature(SECRET, BODY, Some("sha256=")));
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use miette::{miette, Result};
+use regex::Regex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// A release of a project. The fields match the GitHub release format
+/// so that GitHub releases can be deserialised directly.
+#[derive(Deserialize, Debug)]
+pub struct Release {
+    /// The ID of the release in its source, if it has one.
+    #[serde(default)]
+    pub id: Option<u64>,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub body: Option<String>,
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    /// The publication timestamp in ISO 8601 format, missing for drafts.
+    pub published_at: Option<String>,
+    pub assets: Vec<Asset>,
+}
+
+impl Release {
+    pub fn get_assets(&self, file_regex: &Option<Regex>) -> Vec<&Asset> {
+        self.assets
+            .iter()
+            .filter(|asset| {
+                if let Some(regex) = &file_regex {
+                    regex.is_match(asset.name.as_str())
+                } else {
+                    true
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Asset {
+    /// The ID of the asset in its source, if it has one.
+    pub id: Option<u64>,
+    pub url: String,
+    pub name: String,
+    /// The size of the asset file in bytes, if known.
+    pub size: Option<u64>,
+    /// The digest of the asset file in the format `algorithm:hex`,
+    /// for example `sha256:abcd...`. Older assets might not have a digest.
+    pub digest: Option<String>,
+}
+
+impl Asset {
+    /// Checks that the downloaded bytes match the size and digest of this asset.
+    pub fn verify(&self, bytes: &[u8]) -> Result<()> {
+        if let Some(size) = self.size {
+            if bytes.len() as u64 != size {
+                return Err(miette!(
+                    "Size of downloaded asset {} does not match: expected {} bytes, found {}",
+                    self.name,
+                    size,
+                    bytes.len()
+                ));
+            }
+        }
+
+        if let Some(digest) = &self.digest {
+            let (algorithm, expected) = digest
+                .split_once(':')
+                .ok_or_else(|| miette!("Malformed digest for asset {}: {}", self.name, digest))?;
+
+            let actual = match algorithm {
+                "sha256" => hex::encode(Sha256::digest(bytes)),
+                _ => {
+                    return Err(miette!(
+                        "Unsupported digest algorithm for asset {}: {}",
+                        self.name,
+                        algorithm
+                    ))
+                }
+            };
+
+            if !actual.eq_ignore_ascii_case(expected) {
+                return Err(miette!(
+                    "Digest of downloaded asset {} does not match: expected {}, found {}:{}",
+                    self.name,
+                    digest,
+                    algorithm,
+                    actual
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+mod data;
+mod store;
+
+pub use data::*;
+pub use store::*;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::{miette, Result};
+
+use crate::config::Config;
+use crate::gitea::{self, GiteaSource};
+use crate::github::{GitHubSource, Repo};
+use crate::gitlab::{self, GitLabSource};
+use crate::requests::multipart::Form;
+use crate::requests::Context;
+
+/// A place where releases and their asset files are read from,
+/// such as a GitHub repository or a local directory.
+#[async_trait]
+pub trait ReleaseSource: Send + Sync {
+    /// The human-readable name of this kind of source, e.g. `GitHub`.
+    fn name(&self) -> &'static str;
+
+    /// Looks up a release by its tag.
+    async fn get_release(&self, context: &Context, tag: &str) -> Result<Release>;
+
+    /// Lists all releases, newest first.
+    async fn list_releases(&self, _context: &Context) -> Result<Vec<Release>> {
+        Err(miette!(
+            "Listing releases is not supported for {}",
+            self.name()
+        ))
+    }
+
+    /// Fetches the contents of an asset file without verifying them.
+    async fn get_asset_bytes(&self, context: &Context, asset: &Asset) -> Result<Bytes>;
+
+    /// Fetches the contents of an asset file and checks them
+    /// against the size and digest of the asset.
+    async fn download_asset(&self, context: &Context, asset: &Asset) -> Result<Bytes> {
+        let bytes = self.get_asset_bytes(context, asset).await?;
+        asset.verify(&bytes)?;
+        Ok(bytes)
+    }
+
+    /// Downloads an asset file and attaches it to a form.
+    async fn attach_to_form(
+        &self,
+        context: &Context,
+        form: &mut Form,
+        asset: &Asset,
+        field_name: String,
+    ) -> Result<()> {
+        let bytes = self.download_asset(context, asset).await?;
+        form.file(field_name, &asset.name, bytes);
+        Ok(())
+    }
+}
+
+/// Creates the release source defined in the config.
+pub fn from_config(config: &Config) -> Result<Box<dyn ReleaseSource>> {
+    let sources = [&config.github, &config.gitlab, &config.gitea]
+        .iter()
+        .filter(|source| source.is_some())
+        .count();
+
+    if sources > 1 {
+        return Err(miette!(
+            "Only one of github, gitlab and gitea can be set in the config"
+        ));
+    }
+
+    if let Some(project) = &config.gitlab {
+        Ok(Box::new(GitLabSource {
+            instance_url: config
+                .gitlab_url
+                .clone()
+                .unwrap_or(gitlab::DEFAULT_INSTANCE_URL.to_string()),
+            project: project.clone(),
+        }))
+    } else if let Some(repo) = &config.gitea {
+        Ok(Box::new(GiteaSource {
+            instance_url: config
+                .gitea_url
+                .clone()
+                .unwrap_or(gitea::DEFAULT_INSTANCE_URL.to_string()),
+            repo: Repo::parse(repo)?,
+        }))
+    } else {
+        Ok(Box::new(GitHubSource {
+            repo: config.get_github_repo()?,
+        }))
+    }
+}
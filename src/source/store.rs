@@ -0,0 +1,123 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use miette::Result;
+
+use crate::error::MuError;
+use crate::requests::Context;
+use crate::source::{Asset, Release, ReleaseSource};
+
+/// A release source that downloads each asset from another source only once
+/// per run, and optionally keeps the downloaded files in an on-disk cache.
+pub struct AssetStore<'a> {
+    source: &'a dyn ReleaseSource,
+    cache_dir: Option<PathBuf>,
+    assets: Mutex<HashMap<String, Bytes>>,
+}
+
+impl<'a> AssetStore<'a> {
+    /// Creates an asset store that downloads assets from the source.
+    /// If the cache directory is present, assets that have an ID and a digest
+    /// are also stored there and reused across runs.
+    pub fn new(source: &'a dyn ReleaseSource, cache_dir: Option<PathBuf>) -> Self {
+        AssetStore {
+            source,
+            cache_dir,
+            assets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drops the in-memory copies of the assets of a release
+    /// once it won't be published again in this run.
+    pub fn evict(&self, release: &Release) {
+        let mut assets = self.assets.lock().unwrap();
+        for asset in &release.assets {
+            assets.remove(&asset.url);
+        }
+    }
+
+    /// Gets the path of an asset in the cache directory. Assets without an ID
+    /// or a digest aren't cached, since a cached file couldn't be told apart
+    /// from a changed one with the same name.
+    fn cache_path(&self, asset: &Asset) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        let id = asset.id?;
+        let digest = asset.digest.as_ref()?;
+        Some(dir.join(format!("{}-{}", id, digest.replace(':', "-"))))
+    }
+
+    async fn read_cached(&self, asset: &Asset) -> Option<Bytes> {
+        let path = self.cache_path(asset)?;
+        let bytes: Bytes = tokio::fs::read(path).await.ok()?.into();
+
+        // Ignore corrupted cache entries so that they get downloaded again.
+        asset.verify(&bytes).ok()?;
+        Some(bytes)
+    }
+
+    async fn write_cached(&self, asset: &Asset, bytes: &Bytes) -> Result<()> {
+        if let Some(path) = self.cache_path(asset) {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await.map_err(|err| {
+                    MuError::new(format!("Could not create asset cache {}", dir.display()))
+                        .cause(err)
+                        .to_report()
+                })?;
+            }
+
+            tokio::fs::write(&path, bytes).await.map_err(|err| {
+                MuError::new(format!("Could not write {} to asset cache", asset.name))
+                    .cause(err)
+                    .to_report()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReleaseSource for AssetStore<'_> {
+    fn name(&self) -> &'static str {
+        self.source.name()
+    }
+
+    async fn get_release(&self, context: &Context, tag: &str) -> Result<Release> {
+        self.source.get_release(context, tag).await
+    }
+
+    async fn list_releases(&self, context: &Context) -> Result<Vec<Release>> {
+        self.source.list_releases(context).await
+    }
+
+    async fn get_asset_bytes(&self, context: &Context, asset: &Asset) -> Result<Bytes> {
+        if let Some(bytes) = self.assets.lock().unwrap().get(&asset.url) {
+            return Ok(bytes.clone());
+        }
+
+        let bytes = if let Some(bytes) = self.read_cached(asset).await {
+            bytes
+        } else {
+            let bytes = self.source.get_asset_bytes(context, asset).await?;
+            // Only persist files that are known to be intact.
+            asset.verify(&bytes)?;
+            self.write_cached(asset, &bytes).await?;
+            bytes
+        };
+
+        self.assets
+            .lock()
+            .unwrap()
+            .insert(asset.url.clone(), bytes.clone());
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,399 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Debug;
+use std::path::Path;
+
+use miette::{miette, IntoDiagnostic, Result};
+use similar::{ChangeTag, TextDiff};
+
+use crate::config::{Config, ProjectPageSettings};
+use crate::error::MuError;
+use crate::github::absolute_links;
+use crate::modrinth::{
+    find_existing_version, find_missing_files, get_version_data, upload_missing_files,
+    CreateVersionData, EditProjectData, EditVersionData, GetProject, GetProjectVersions,
+    ModifyProject, ModifyVersion, ModrinthProject, Version,
+};
+use crate::progress::confirm;
+use crate::requests::{ApiRequest, Context};
+use crate::source::ReleaseSource;
+
+pub struct SyncOptions {
+    /// Skip the confirmation prompt.
+    pub assume_yes: bool,
+}
+
+/// Updates the metadata of the existing Modrinth versions of a release
+/// to match the release and the config, and adds the assets
+/// that are missing from the versions.
+pub async fn sync(
+    context: &Context,
+    source: &dyn ReleaseSource,
+    config: &Config,
+    tag: &str,
+    options: &SyncOptions,
+) -> Result<()> {
+    let release = source.get_release(context, tag).await?;
+
+    for project in config.get_projects() {
+        let Some(settings) = project.get_modrinth(config) else {
+            continue;
+        };
+
+        let data = get_version_data(context, config, &project, &release, settings, vec![]).await?;
+        let versions = GetProjectVersions {
+            api_url: settings.api_url(),
+            project_id: &settings.project_id,
+        }
+        .request(context)
+        .await?;
+        let existing = find_existing_version(&versions, &data.version_number, &data.loaders)?;
+
+        let Some(existing) = existing else {
+            context
+                .progress
+                .println(format!(
+                    "{} version {} does not exist on Modrinth project {}, skipping",
+                    console::style("Warning:").bold().yellow(),
+                    data.version_number,
+                    settings.project_id
+                ))
+                .into_diagnostic()?;
+            continue;
+        };
+
+        let mut diff = Vec::new();
+        let changes = get_changes(existing, &data, &mut diff);
+        let changed = !diff.is_empty();
+        let missing =
+            find_missing_files(context, source, config, &project, &release, existing).await?;
+
+        if !missing.is_empty() {
+            let files: Vec<String> = missing
+                .iter()
+                .map(|(asset, _)| {
+                    console::style(format!("+ {}", asset.name))
+                        .green()
+                        .to_string()
+                })
+                .collect();
+            diff.push(format!("files:\n{}", files.join("\n")));
+        }
+
+        if diff.is_empty() {
+            context
+                .progress
+                .println(format!(
+                    "Version {} of Modrinth project {} is up to date",
+                    data.version_number, settings.project_id
+                ))
+                .into_diagnostic()?;
+            continue;
+        }
+
+        // Write to the standard streams since this output is
+        // part of the interactive confirmation.
+        println!(
+            "Changes to version {} of Modrinth project {}:\n{}",
+            data.version_number,
+            settings.project_id,
+            diff.join("\n")
+        );
+
+        if !options.assume_yes && !confirm().await? {
+            return Err(miette!("Sync cancelled"));
+        }
+
+        if changed {
+            ModifyVersion {
+                api_url: settings.api_url(),
+                version_id: &existing.id,
+                data: &changes,
+            }
+            .request(context)
+            .await?;
+        }
+
+        upload_missing_files(context, settings, existing, missing).await?;
+    }
+
+    Ok(())
+}
+
+/// Computes the changed fields of a version and describes them in `diff`.
+fn get_changes(
+    existing: &Version,
+    data: &CreateVersionData,
+    diff: &mut Vec<String>,
+) -> EditVersionData {
+    EditVersionData {
+        name: compare("name", &existing.name, &data.name, diff),
+        changelog: compare_text("changelog", &existing.changelog, &data.changelog, diff),
+        dependencies: compare_unordered(
+            "dependencies",
+            &existing.dependencies,
+            &data.dependencies,
+            diff,
+        ),
+        game_versions: compare_unordered(
+            "game_versions",
+            &existing.game_versions,
+            &data.game_versions,
+            diff,
+        ),
+        version_type: compare(
+            "version_type",
+            &existing.version_type,
+            &data.version_type,
+            diff,
+        ),
+        loaders: compare_unordered("loaders", &existing.loaders, &data.loaders, diff),
+        featured: compare("featured", &existing.featured, &data.featured, diff),
+        status: None,
+    }
+}
+
+fn compare<T: PartialEq + Debug + Clone>(
+    field: &str,
+    old: &T,
+    new: &T,
+    diff: &mut Vec<String>,
+) -> Option<T> {
+    if old == new {
+        return None;
+    }
+
+    diff.push(format!(
+        "{}:\n{}\n{}",
+        field,
+        console::style(format!("- {:?}", old)).red(),
+        console::style(format!("+ {:?}", new)).green()
+    ));
+    Some(new.clone())
+}
+
+/// Compares lists whose order doesn't matter.
+fn compare_unordered<T: PartialEq + Debug + Clone>(
+    field: &str,
+    old: &Vec<T>,
+    new: &Vec<T>,
+    diff: &mut Vec<String>,
+) -> Option<Vec<T>> {
+    let same = old.len() == new.len()
+        && old.iter().all(|item| new.contains(item))
+        && new.iter().all(|item| old.contains(item));
+
+    if same {
+        return None;
+    }
+
+    compare(field, old, new, diff)
+}
+
+fn compare_text(
+    field: &str,
+    old: &Option<String>,
+    new: &Option<String>,
+    diff: &mut Vec<String>,
+) -> Option<String> {
+    let old_text = old.as_deref().unwrap_or_default();
+    let new_text = new.as_deref().unwrap_or_default();
+
+    if old_text == new_text {
+        return None;
+    }
+
+    let mut lines = vec![format!("{}:", field)];
+
+    for change in TextDiff::from_lines(old_text, new_text).iter_all_changes() {
+        let line = change.value().trim_end_matches('\n');
+        lines.push(match change.tag() {
+            ChangeTag::Delete => console::style(format!("- {}", line)).red().to_string(),
+            ChangeTag::Insert => console::style(format!("+ {}", line)).green().to_string(),
+            ChangeTag::Equal => format!("  {}", line),
+        });
+    }
+
+    diff.push(lines.join("\n"));
+    Some(new_text.to_string())
+}
+
+pub struct SyncPageOptions {
+    /// Only print the changes.
+    pub dry_run: bool,
+    /// Skip the confirmation prompt.
+    pub assume_yes: bool,
+}
+
+/// Updates the pages of the Modrinth projects that have page settings
+/// to match the repository. The changes are printed as a diff and
+/// applied after confirmation unless this is a dry run.
+pub async fn sync_pages(
+    context: &Context,
+    config: &Config,
+    options: &SyncPageOptions,
+) -> Result<()> {
+    let mut synced = false;
+
+    for project in config.get_projects() {
+        let Some(settings) = project.get_modrinth(config) else {
+            continue;
+        };
+        let Some(page) = &settings.page else {
+            continue;
+        };
+        synced = true;
+
+        let existing = GetProject {
+            api_url: settings.api_url(),
+            id_or_slug: &settings.project_id,
+        }
+        .request(context)
+        .await?;
+        let body = read_body(config, page).await?;
+        let mut diff = Vec::new();
+        let changes = get_page_changes(&existing, page, body, &mut diff);
+
+        if diff.is_empty() {
+            context
+                .progress
+                .println(format!(
+                    "Page of Modrinth project {} is up to date",
+                    settings.project_id
+                ))
+                .into_diagnostic()?;
+            continue;
+        }
+
+        // Write to the standard streams since this output is
+        // part of the interactive confirmation.
+        println!(
+            "Changes to page of Modrinth project {}:\n{}",
+            settings.project_id,
+            diff.join("\n")
+        );
+
+        if options.dry_run {
+            continue;
+        }
+
+        if !options.assume_yes && !confirm().await? {
+            return Err(miette!("Sync cancelled"));
+        }
+
+        ModifyProject {
+            api_url: settings.api_url(),
+            project_id: &settings.project_id,
+            data: &changes,
+        }
+        .request(context)
+        .await?;
+    }
+
+    if !synced {
+        return Err(miette!("No Modrinth project has page settings"));
+    }
+
+    Ok(())
+}
+
+/// Reads the body file with its relative links made absolute.
+async fn read_body(config: &Config, page: &ProjectPageSettings) -> Result<Option<String>> {
+    let Some(path) = &page.body_file else {
+        return Ok(None);
+    };
+
+    let text = tokio::fs::read_to_string(path).await.map_err(|err| {
+        MuError::new(format!("Could not read page body {}", path.display()))
+            .cause(err)
+            .to_report()
+    })?;
+    let repo = config.get_github_repo()?;
+    let base_dir = repo_relative_dir(path)?;
+    let git_ref = page.git_ref.as_deref().unwrap_or("HEAD");
+    Ok(Some(absolute_links(&text, &repo, git_ref, &base_dir)))
+}
+
+/// Gets the directory of a file relative to the root of the git repository it is in.
+fn repo_relative_dir(path: &Path) -> Result<String> {
+    let path = path.canonicalize().map_err(|err| {
+        MuError::new(format!("Could not resolve page body {}", path.display()))
+            .cause(err)
+            .to_report()
+    })?;
+    let dir = path.parent().unwrap_or(&path);
+    let root = dir
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .ok_or_else(|| miette!("{} is not in a git repository", path.display()))?;
+    let relative = dir.strip_prefix(root).unwrap_or(Path::new(""));
+    Ok(relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Computes the changed fields of a project page and describes them in `diff`.
+fn get_page_changes(
+    existing: &ModrinthProject,
+    page: &ProjectPageSettings,
+    body: Option<String>,
+    diff: &mut Vec<String>,
+) -> EditProjectData {
+    EditProjectData {
+        body: body
+            .and_then(|body| compare_text("body", &Some(existing.body.clone()), &Some(body), diff)),
+        description: compare_setting("summary", &existing.description, &page.summary, diff),
+        categories: compare_unordered_setting(
+            "categories",
+            &existing.categories,
+            &page.categories,
+            diff,
+        ),
+        additional_categories: compare_unordered_setting(
+            "additional_categories",
+            &existing.additional_categories,
+            &page.additional_categories,
+            diff,
+        ),
+        license_id: compare_setting("license", &existing.license.id, &page.license, diff),
+        source_url: compare_url("source_url", &existing.source_url, &page.source_url, diff),
+        issues_url: compare_url("issues_url", &existing.issues_url, &page.issues_url, diff),
+        wiki_url: compare_url("wiki_url", &existing.wiki_url, &page.wiki_url, diff),
+        discord_url: compare_url(
+            "discord_url",
+            &existing.discord_url,
+            &page.discord_url,
+            diff,
+        ),
+    }
+}
+
+fn compare_setting<T: PartialEq + Debug + Clone>(
+    field: &str,
+    old: &T,
+    new: &Option<T>,
+    diff: &mut Vec<String>,
+) -> Option<T> {
+    new.as_ref().and_then(|new| compare(field, old, new, diff))
+}
+
+fn compare_unordered_setting<T: PartialEq + Debug + Clone>(
+    field: &str,
+    old: &Vec<T>,
+    new: &Option<Vec<T>>,
+    diff: &mut Vec<String>,
+) -> Option<Vec<T>> {
+    new.as_ref()
+        .and_then(|new| compare_unordered(field, old, new, diff))
+}
+
+fn compare_url(
+    field: &str,
+    old: &Option<String>,
+    new: &Option<String>,
+    diff: &mut Vec<String>,
+) -> Option<String> {
+    compare_setting(field, old, &new.clone().map(Some), diff).flatten()
+}
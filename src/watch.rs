@@ -0,0 +1,206 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use miette::{miette, IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::error::MuError;
+use crate::github::{PollReleases, ReleasesPoll};
+use crate::publish::mirror_release;
+use crate::requests::{ApiRequest, Context};
+
+/// The longest time to wait between polls after repeated errors.
+const MAX_ERROR_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+pub struct WatchOptions {
+    /// The time to wait between polls.
+    pub interval: Duration,
+    /// The file that keeps track of the mirrored tags.
+    pub state_file: PathBuf,
+    /// Directory for caching downloaded assets across runs.
+    pub asset_cache: Option<PathBuf>,
+}
+
+/// The persistent state of the watch mode.
+#[derive(Serialize, Deserialize, Default)]
+struct WatchState {
+    /// The state of each watched repository, keyed by `owner/repo`.
+    repositories: BTreeMap<String, RepoState>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RepoState {
+    /// The tags that have already been mirrored or were present
+    /// when the repository was first watched.
+    mirrored: BTreeSet<String>,
+    /// The tags that could not be mirrored and are retried on every poll.
+    #[serde(default)]
+    failed: BTreeSet<String>,
+    /// The ETag of the last poll.
+    etag: Option<String>,
+}
+
+impl WatchState {
+    async fn read(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let text = tokio::fs::read_to_string(path).await.map_err(|err| {
+            MuError::new(format!("Could not read watch state {}", path.display()))
+                .cause(err)
+                .to_report()
+        })?;
+        serde_json::from_str(&text).into_diagnostic().map(Some)
+    }
+
+    async fn write(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(self).into_diagnostic()?;
+        tokio::fs::write(path, text).await.map_err(|err| {
+            MuError::new(format!("Could not write watch state {}", path.display()))
+                .cause(err)
+                .to_report()
+        })
+    }
+}
+
+/// Periodically polls the GitHub releases of the configured repository
+/// and mirrors the ones that haven't been mirrored yet.
+///
+/// When the state file has no entry for the repository, the current releases are recorded
+/// as already mirrored so that only releases published later are mirrored.
+pub async fn watch(context: &Context, config: &Config, options: &WatchOptions) -> Result<()> {
+    if config.gitlab.is_some() || config.gitea.is_some() {
+        return Err(miette!(
+            "Watching is only supported for GitHub repositories"
+        ));
+    }
+
+    config.reject_publish_at("watch")?;
+    let repo = config.get_github_repo()?;
+    let repo_name = format!("{}/{}", repo.owner, repo.name);
+    let mut state = WatchState::read(&options.state_file)
+        .await?
+        .unwrap_or_default();
+    let mut error_backoff = options.interval;
+
+    loop {
+        let poll = PollReleases {
+            repo: &repo,
+            etag: state
+                .repositories
+                .get(&repo_name)
+                .and_then(|repo_state| repo_state.etag.as_deref()),
+        }
+        .request(context)
+        .await;
+
+        let delay = match poll {
+            Ok(poll) => {
+                error_backoff = options.interval;
+
+                if state.repositories.contains_key(&repo_name) {
+                    process_poll(context, config, options, &mut state, &repo_name, poll).await?;
+                } else {
+                    let Some(releases) = poll.releases else {
+                        // Rate limited before the first successful poll.
+                        tokio::time::sleep(options.interval).await;
+                        continue;
+                    };
+                    let repo_state = RepoState {
+                        // Drafts are left out so that they are mirrored once published.
+                        mirrored: releases
+                            .into_iter()
+                            .filter(|release| !release.draft)
+                            .map(|release| release.tag_name)
+                            .collect(),
+                        failed: BTreeSet::new(),
+                        etag: poll.etag,
+                    };
+                    println!(
+                        "Watching {}, {} existing releases will not be mirrored",
+                        repo_name,
+                        repo_state.mirrored.len()
+                    );
+                    state.repositories.insert(repo_name.clone(), repo_state);
+                    state.write(&options.state_file).await?;
+                }
+
+                options.interval
+            }
+            Err(err) => {
+                eprintln!(
+                    "{} Could not poll releases: {:?}",
+                    console::style("Error:").bold().red(),
+                    err
+                );
+                let delay = error_backoff;
+                error_backoff = (error_backoff * 2).min(MAX_ERROR_BACKOFF);
+                delay
+            }
+        };
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Mirrors the new releases of a poll and retries the ones that failed before.
+async fn process_poll(
+    context: &Context,
+    config: &Config,
+    options: &WatchOptions,
+    state: &mut WatchState,
+    repo_name: &str,
+    poll: ReleasesPoll,
+) -> Result<()> {
+    let repo_state = state.repositories.get_mut(repo_name).unwrap();
+    // The ETag is always kept, since failed tags are retried separately.
+    repo_state.etag = poll.etag;
+
+    // Retry failed tags first, since they are older than the new releases.
+    let mut tags: Vec<String> = repo_state.failed.iter().cloned().collect();
+
+    // Releases are listed newest first.
+    for release in poll.releases.iter().flatten().rev() {
+        if !release.draft
+            && !repo_state.mirrored.contains(&release.tag_name)
+            && !repo_state.failed.contains(&release.tag_name)
+        {
+            tags.push(release.tag_name.clone());
+        }
+    }
+
+    for tag in tags {
+        println!("Mirroring release {}", tag);
+        let result = mirror_release(context, config, &tag, options.asset_cache.clone()).await;
+        let repo_state = state.repositories.get_mut(repo_name).unwrap();
+
+        match result {
+            Ok(_) => {
+                repo_state.failed.remove(&tag);
+                repo_state.mirrored.insert(tag);
+            }
+            Err(err) => {
+                eprintln!(
+                    "{} Could not mirror {}: {:?}",
+                    console::style("Error:").bold().red(),
+                    tag,
+                    err
+                );
+                repo_state.failed.insert(tag);
+            }
+        }
+
+        state.write(&options.state_file).await?;
+    }
+
+    state.write(&options.state_file).await
+}